@@ -1,4 +1,15 @@
+use std::collections::HashSet;
+
 use bevy::prelude::*;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{
+    CROSS_WORLD_HALF, DEFAULT_MAP_PATH, MAP_BOX_SCALE, MAX_PITCH, MAX_PLAYER_COUNT, MAX_REWIND,
+    NET_INPUT_QUEUE, NET_TIMEOUT, ROLLBACK_SEND_LIMIT, ROLLBACK_WINDOW, SETTINGS_VERSION,
+};
+use crate::helpers::PeerTransport;
 
 #[derive(Component)]
 pub struct Player {
@@ -13,39 +24,691 @@ pub struct Player {
 #[derive(Component, Deref, DerefMut)]
 pub struct Velocity(pub Vec3);
 
+/// Translation before the latest fixed tick; rendering blends from it towards
+/// the current `Transform` so motion stays smooth between ticks.
+#[derive(Component, Default, Deref, DerefMut)]
+pub struct PreviousTranslation(pub Vec3);
+
+/// Number of fixed ticks simulated so far.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq, Deref, DerefMut)]
+pub struct SimTick(pub u64);
+
+/// Per-tick input generator replacing devices in headless runs and replays:
+/// given the tick and the player id, returns that player's input.
+#[derive(Resource)]
+pub struct InputScript(pub Box<dyn Fn(u64, usize) -> TickInput + Send + Sync>);
+
+/// A recorded match: its setup plus every player's input on every tick, which
+/// is all the simulation needs to play it again exactly.
+#[derive(Resource, Clone, Debug, Serialize, Deserialize)]
+pub struct Demo {
+    pub version: u32,
+    pub seed: u64,
+    pub tick_rate: f64,
+    /// Asset path of the map, as given to `--map`; the map itself is in
+    /// `maps`, so later edits to the file do not change the replay.
+    pub map: String,
+    pub rules: MatchRules,
+    /// The map in play from each tick on, starting on tick 0; later entries
+    /// are edits reloaded while recording.
+    pub maps: Vec<(u64, MapDef)>,
+    /// The physics tuning from each tick on, likewise.
+    pub physics: Vec<(u64, PhysicsConfig)>,
+    /// Player slots in the match, counted like the scoreboard's contenders.
+    pub players: usize,
+    /// `ticks[t][id]` is the input of player `id` on tick `t`.
+    pub ticks: Vec<Vec<TickInput>>,
+    /// `view_ticks[t][id]` is the `ViewTick` player `id` shot with on tick
+    /// `t`, for remote players whose hits were lag compensated.
+    pub view_ticks: Vec<Vec<Option<u64>>>,
+}
+
+impl Demo {
+    /// Recorded input, or no input past the end of the recording.
+    pub fn input(&self, tick: u64, player: usize) -> TickInput {
+        self.ticks
+            .get(tick as usize)
+            .and_then(|inputs| inputs.get(player))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Recorded view tick, if the player saw the world late on `tick`.
+    pub fn view_tick(&self, tick: u64, player: usize) -> Option<u64> {
+        self.view_ticks
+            .get(tick as usize)
+            .and_then(|views| views.get(player))
+            .copied()
+            .flatten()
+    }
+
+    /// The map that comes into play on `tick`, if it changed then.
+    pub fn map_change(&self, tick: u64) -> Option<&MapDef> {
+        self.maps.iter().find(|(t, _)| *t == tick).map(|(_, map)| map)
+    }
+
+    /// The physics tuning that comes into force on `tick`, if it changed then.
+    pub fn physics_change(&self, tick: u64) -> Option<PhysicsConfig> {
+        self.physics
+            .iter()
+            .find(|(t, _)| *t == tick)
+            .map(|(_, physics)| *physics)
+    }
+}
+
+/// Demo being recorded with `--record`, written to `path` when the match ends
+/// or the app exits.
+#[derive(Resource)]
+pub struct DemoRecorder {
+    pub path: std::path::PathBuf,
+    /// Started on the first tick, once the match setup is final.
+    pub demo: Option<Demo>,
+    pub finished: bool,
+}
+
+impl DemoRecorder {
+    pub fn new(path: impl Into<std::path::PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            demo: None,
+            finished: false,
+        }
+    }
+}
+
+/// Everything the simulation reads from a player on one fixed tick. Sampled
+/// from `ActionState` each frame; `fire` is latched until a tick consumes it.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TickInput {
+    pub movement: Vec2,
+    pub jump: bool,
+    pub fire: bool,
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+impl TickInput {
+    /// Direction the player is looking in.
+    pub fn forward(&self) -> Vec3 {
+        crate::helpers::look_direction(self.yaw, self.pitch)
+    }
+
+    /// The input as local devices could have produced it, for input from the
+    /// network: non-finite values zeroed, movement no longer than 1 and pitch
+    /// within `MAX_PITCH`.
+    pub fn sanitized(self) -> Self {
+        let finite = |v: f32| if v.is_finite() { v } else { 0.0 };
+        let movement = if self.movement.is_finite() {
+            self.movement.clamp_length_max(1.0)
+        } else {
+            Vec2::ZERO
+        };
+        Self {
+            movement,
+            yaw: finite(self.yaw),
+            pitch: finite(self.pitch).clamp(-MAX_PITCH, MAX_PITCH),
+            ..self
+        }
+    }
+}
+
+/// Tuning of an AI player. Angles are in radians, times in seconds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BotProfile {
+    /// How long an opponent must stay in sight before the bot reacts.
+    pub reaction_time: f32,
+    /// Largest random aim offset per axis, rerolled after every shot.
+    pub aim_error: f32,
+    pub turn_speed: f32,
+    pub fire_interval: f32,
+}
+
+/// Preset `BotProfile`s selectable per player slot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BotDifficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl BotDifficulty {
+    pub fn profile(self) -> BotProfile {
+        match self {
+            BotDifficulty::Easy => BotProfile {
+                reaction_time: 0.6,
+                aim_error: 0.12,
+                turn_speed: 2.5,
+                fire_interval: 1.0,
+            },
+            BotDifficulty::Normal => BotProfile {
+                reaction_time: 0.35,
+                aim_error: 0.06,
+                turn_speed: 4.0,
+                fire_interval: 0.7,
+            },
+            BotDifficulty::Hard => BotProfile {
+                reaction_time: 0.2,
+                aim_error: 0.025,
+                turn_speed: 7.0,
+                fire_interval: 0.45,
+            },
+        }
+    }
+
+    /// Steps through off, easy, normal and hard, wrapping around.
+    pub fn cycle(current: Option<Self>, steps: i32) -> Option<Self> {
+        const ORDER: [Option<BotDifficulty>; 4] = [
+            None,
+            Some(BotDifficulty::Easy),
+            Some(BotDifficulty::Normal),
+            Some(BotDifficulty::Hard),
+        ];
+        let index = ORDER.iter().position(|d| *d == current).unwrap_or(0) as i32;
+        ORDER[(index + steps).rem_euclid(ORDER.len() as i32) as usize]
+    }
+}
+
+/// AI controller of a player; `drive_bots` writes its `TickInput` each tick
+/// in place of devices.
+#[derive(Component, Clone, Debug)]
+pub struct Bot {
+    pub profile: BotProfile,
+    /// Opponent being tracked and how long it has been in sight.
+    pub target: Option<usize>,
+    pub seen_for: f32,
+    /// Yaw and pitch added to the true aim.
+    pub aim_offset: Vec2,
+    pub cooldown: f32,
+    /// Floor point the bot walks to when nobody is in sight.
+    pub goal: Option<Vec3>,
+    pub goal_time: f32,
+    pub strafe: f32,
+    /// Separate from `MatchRng` so replays, where bots do not run, draw the
+    /// same respawns.
+    pub rng: StdRng,
+}
+
+impl Bot {
+    pub fn new(profile: BotProfile, seed: u64) -> Self {
+        Self {
+            profile,
+            target: None,
+            seen_for: 0.0,
+            aim_offset: Vec2::ZERO,
+            cooldown: 0.0,
+            goal: None,
+            goal_time: 0.0,
+            strafe: 1.0,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+#[derive(Component, Deref, DerefMut)]
+pub struct Health(pub f32);
+
+/// Present while a player is dead; removed by the respawn system when the
+/// timer finishes.
+#[derive(Component)]
+pub struct Dead {
+    pub respawn: Timer,
+}
+
+/// Seeded RNG for everything random in a match, so a seed reproduces it.
+#[derive(Resource)]
+pub struct MatchRng {
+    pub seed: u64,
+    pub rng: StdRng,
+}
+
+impl MatchRng {
+    pub fn seeded(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Default for MatchRng {
+    fn default() -> Self {
+        Self::seeded(rand::random())
+    }
+}
+
+/// Designated respawn positions on the arena floor (the feet touch the point).
+/// When empty, respawns sample the floor plane instead.
+#[derive(Resource, Clone, Debug)]
+pub struct SpawnPoints(pub Vec<Vec3>);
+
+impl Default for SpawnPoints {
+    fn default() -> Self {
+        let r = MAP_BOX_SCALE as f32 * 0.75;
+        let floor = -(MAP_BOX_SCALE as f32);
+        let mut points = Vec::new();
+        for x in [-r, 0.0, r] {
+            for z in [-r, 0.0, r] {
+                if x != 0.0 || z != 0.0 {
+                    points.push(Vec3::new(x, floor, z));
+                }
+            }
+        }
+        Self(points)
+    }
+}
+
+/// Damage and respawn rules for a match.
+#[derive(Resource, Clone, Debug, Serialize, Deserialize)]
+pub struct MatchRules {
+    pub max_health: f32,
+    pub body_damage: f32,
+    pub head_damage: f32,
+    pub respawn_delay: f32,       // seconds
+    pub score_limit: Option<u32>, // kills needed to win
+    pub time_limit: Option<f32>,  // seconds
+    /// Furthest back in seconds a shot may be checked against where targets
+    /// were, to make up for a remote shooter's lag. At most `MAX_REWIND`.
+    #[serde(
+        default = "default_max_rewind",
+        deserialize_with = "deserialize_max_rewind"
+    )]
+    pub max_rewind: f32,
+}
+
+fn default_max_rewind() -> f32 {
+    0.2
+}
+
+fn deserialize_max_rewind<'de, D: serde::Deserializer<'de>>(d: D) -> Result<f32, D::Error> {
+    f32::deserialize(d).map(MatchRules::clamp_rewind)
+}
+
+impl Default for MatchRules {
+    fn default() -> Self {
+        Self {
+            max_health: 100.0,
+            body_damage: 34.0,
+            head_damage: 100.0,
+            respawn_delay: 3.0,
+            score_limit: Some(20),
+            time_limit: Some(600.0),
+            max_rewind: default_max_rewind(),
+        }
+    }
+}
+
+impl MatchRules {
+    /// `secs` as a rewind window, held within `0..=MAX_REWIND`; NaN is none.
+    pub fn clamp_rewind(secs: f32) -> f32 {
+        if secs.is_nan() {
+            0.0
+        } else {
+            secs.clamp(0.0, MAX_REWIND)
+        }
+    }
+
+    /// Any hit kills and the target respawns immediately, like the original.
+    pub fn instagib() -> Self {
+        Self {
+            max_health: 1.0,
+            body_damage: 1.0,
+            head_damage: 1.0,
+            respawn_delay: 0.0,
+            ..Self::default()
+        }
+    }
+}
+
+/// A physical or virtual input device that a player can claim.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InputDevice {
+    Mouse,
+    Keyboard(usize), // index into ActionBindings::keyboards
+    Gamepad(Entity),
+}
+
+/// Devices claimed by a player. Input-consuming systems read from this
+/// instead of matching on `Player::id`.
+#[derive(Component, Default)]
+pub struct InputAssignment {
+    pub mouse: bool,
+    pub keyboard: Option<usize>,
+    pub gamepad: Option<Entity>,
+}
+
+impl InputAssignment {
+    pub fn owns(&self, device: InputDevice) -> bool {
+        match device {
+            InputDevice::Mouse => self.mouse,
+            InputDevice::Keyboard(k) => self.keyboard == Some(k),
+            InputDevice::Gamepad(e) => self.gamepad == Some(e),
+        }
+    }
+
+    /// Whether a device of the same kind is already claimed.
+    pub fn has_kind(&self, device: InputDevice) -> bool {
+        match device {
+            InputDevice::Mouse => self.mouse,
+            InputDevice::Keyboard(_) => self.keyboard.is_some(),
+            InputDevice::Gamepad(_) => self.gamepad.is_some(),
+        }
+    }
+
+    /// Whether no device has been claimed, i.e. nobody has joined this slot.
+    pub fn is_empty(&self) -> bool {
+        !self.mouse && self.keyboard.is_none() && self.gamepad.is_none()
+    }
+
+    pub fn claim(&mut self, device: InputDevice) {
+        match device {
+            InputDevice::Mouse => self.mouse = true,
+            InputDevice::Keyboard(k) => self.keyboard = Some(k),
+            InputDevice::Gamepad(e) => self.gamepad = Some(e),
+        }
+    }
+}
+
 #[derive(Component)]
 pub struct PlayerCamera {
     pub player_id: usize,
 }
 
+/// What a spectator camera is looking through.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpectatorMode {
+    FreeFly,
+    Follow(usize),
+}
+
+/// A camera not bound to a player: flies freely or follows any player in
+/// first or third person. Driven by its own `InputAssignment` and
+/// `ActionState`; `Fire` cycles the target, `Jump` toggles third person.
+#[derive(Component, Clone, Debug)]
+pub struct Spectator {
+    pub mode: SpectatorMode,
+    pub third_person: bool,
+    /// Free-fly view angles; following uses the target's.
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+impl Default for Spectator {
+    fn default() -> Self {
+        Self {
+            mode: SpectatorMode::FreeFly,
+            third_person: false,
+            yaw: 0.0,
+            pitch: -0.3,
+        }
+    }
+}
+
+/// Spawn a spectator camera at startup, from `--spectate` or `--replay`.
+#[derive(Resource)]
+pub struct SpectatorEnabled;
+
 #[derive(Resource)]
 pub struct PlayerCount(pub usize);
 
 #[derive(Resource)]
 pub struct Edges(pub Vec<(Vec3, Vec3)>);
 
+/// Solid axis-aligned box inside the arena.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Obstacle {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Obstacle {
+    pub fn new(min: Vec3, max: Vec3) -> Self {
+        Self { min, max }
+    }
+
+    pub fn center(&self) -> Vec3 {
+        (self.min + self.max) * 0.5
+    }
+
+    pub fn size(&self) -> Vec3 {
+        self.max - self.min
+    }
+}
+
+/// World geometry: a cube of `half_extent` with solid obstacles inside.
+#[derive(Resource, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Arena {
+    pub half_extent: f32,
+    pub obstacles: Vec<Obstacle>,
+}
+
+impl Arena {
+    /// Empty cube, as in the original game.
+    pub fn empty(half_extent: f32) -> Self {
+        Self {
+            half_extent,
+            obstacles: Vec::new(),
+        }
+    }
+
+    pub fn floor(&self) -> f32 {
+        -self.half_extent
+    }
+}
+
+impl Default for Arena {
+    /// Four pillars around a raised centre platform reached by a staircase ramp.
+    fn default() -> Self {
+        let mut arena = Self::empty(MAP_BOX_SCALE as f32);
+        let floor = arena.floor();
+        for (x, z) in [(-6.0, -6.0), (6.0, -6.0), (-6.0, 6.0), (6.0, 6.0)] {
+            arena.obstacles.push(Obstacle::new(
+                Vec3::new(x - 1.0, floor, z - 1.0),
+                Vec3::new(x + 1.0, 0.0, z + 1.0),
+            ));
+        }
+        let platform_top = floor + 5.0;
+        arena.obstacles.push(Obstacle::new(
+            Vec3::new(-3.0, platform_top - 1.0, -3.0),
+            Vec3::new(3.0, platform_top, 3.0),
+        ));
+        // Steps low enough for the feet sphere to climb without jumping
+        let (steps, depth) = (20, 0.35);
+        for k in 0..steps {
+            let x = 3.0 + depth * (steps - 1 - k) as f32;
+            arena.obstacles.push(Obstacle::new(
+                Vec3::new(x, floor, -1.0),
+                Vec3::new(x + depth, floor + 5.0 * (k + 1) as f32 / steps as f32, 1.0),
+            ));
+        }
+        arena
+    }
+}
+
+/// Colors the world is drawn with.
+#[derive(Resource, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MapColors {
+    pub edges: Color,
+    pub obstacles: Color,
+}
+
+impl Default for MapColors {
+    fn default() -> Self {
+        Self {
+            edges: Color::srgb(0.25, 0.25, 0.25),
+            obstacles: Color::srgb(0.5, 0.5, 0.5),
+        }
+    }
+}
+
+/// Map file (`*.map.ron` under `assets/`): arena bounds, obstacles, spawn
+/// points, floor grid density and colors. Missing fields take the built-in map,
+/// except that a map without obstacles is open, one without spawn points
+/// spawns anywhere on the open floor, and one without `grid_lines` has a line
+/// every two units. As a resource, the map in play.
+#[derive(Asset, Resource, TypePath, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MapDef {
+    pub half_extent: f32,
+    #[serde(default)]
+    pub grid_lines: usize, // per floor axis; 0 in a file to derive it
+    #[serde(default)]
+    pub obstacles: Vec<Obstacle>,
+    #[serde(default)]
+    pub spawn_points: Vec<Vec3>,
+    pub colors: MapColors,
+}
+
+impl Default for MapDef {
+    fn default() -> Self {
+        let arena = Arena::default();
+        Self {
+            half_extent: arena.half_extent,
+            grid_lines: Self::grid_lines_for(arena.half_extent),
+            obstacles: arena.obstacles,
+            spawn_points: SpawnPoints::default().0,
+            colors: MapColors::default(),
+        }
+    }
+}
+
+impl MapDef {
+    /// Floor lines per axis for a line every two units.
+    pub fn grid_lines_for(half_extent: f32) -> usize {
+        (half_extent.round() as usize).max(1)
+    }
+
+    pub fn arena(&self) -> Arena {
+        Arena {
+            half_extent: self.half_extent,
+            obstacles: self.obstacles.clone(),
+        }
+    }
+}
+
+/// Movement tuning, loaded from `assets/physics/default.physics.ron` and
+/// reloaded whenever the file changes.
+#[derive(Asset, Resource, TypePath, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicsConfig {
+    pub drag_rate: f32,
+    pub move_mult: f32,
+    pub gravity: f32,
+    pub jump_velocity: f32,
+}
+
+impl Default for PhysicsConfig {
+    fn default() -> Self {
+        Self {
+            drag_rate: 6.0,
+            move_mult: 60.0,
+            gravity: 25.0,
+            jump_velocity: 8.4375,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct PhysicsConfigHandle(pub Handle<PhysicsConfig>);
+
+/// The map asset in use; `apply_map` copies it into the world when it loads.
+#[derive(Resource)]
+pub struct MapHandle(pub Handle<MapDef>);
+
+/// Asset path of the map to load, from `--map`.
+#[derive(Resource)]
+pub struct MapPath(pub String);
+
+impl Default for MapPath {
+    fn default() -> Self {
+        Self(DEFAULT_MAP_PATH.into())
+    }
+}
+
 #[derive(Resource, Default)]
 pub struct MouseDelta {
     pub dx: f32,
     pub dy: f32,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(States, Default, Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum AppMode {
+    #[default]
     Playing,
     Menu,
     Settings,
+    Bindings,
+    MatchOver,
 }
 
-#[derive(Resource)]
-pub struct GameMode(pub AppMode);
+impl AppMode {
+    /// Page reached by backing out of this one.
+    pub fn back(self) -> AppMode {
+        match self {
+            AppMode::Playing => AppMode::Menu,
+            AppMode::Menu => AppMode::Playing,
+            AppMode::Settings => AppMode::Menu,
+            AppMode::Bindings => AppMode::Settings,
+            AppMode::MatchOver => AppMode::Playing, // starts a new match
+        }
+    }
+}
 
-#[derive(Resource)]
-pub struct SettingsRes {
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlayerSettings {
     pub sensitivity: f32,
     pub crosshair_half: f32,
+    pub gamepad_sensitivity: f32, // radians per second at full stick deflection
+    pub gamepad_dead_zone: f32,
+    /// AI difficulty used while no one has joined this slot, Normal unless
+    /// set; `None` stands still.
+    pub bot: Option<BotDifficulty>,
+}
+
+impl Default for PlayerSettings {
+    fn default() -> Self {
+        Self {
+            sensitivity: 0.0025,
+            crosshair_half: CROSS_WORLD_HALF,
+            gamepad_sensitivity: 3.0,
+            gamepad_dead_zone: 0.15,
+            bot: Some(BotDifficulty::Normal),
+        }
+    }
+}
+
+/// Per-player settings, persisted to `SETTINGS_PATH` whenever they change.
+#[derive(Resource, Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SettingsRes {
+    pub version: u32,
+    pub players: Vec<PlayerSettings>,
+}
+
+impl Default for SettingsRes {
+    fn default() -> Self {
+        Self {
+            version: SETTINGS_VERSION,
+            // Slots nobody joins practice against bots out of the box
+            players: vec![PlayerSettings::default(); MAX_PLAYER_COUNT],
+        }
+    }
+}
+
+impl SettingsRes {
+    pub fn player(&self, id: usize) -> &PlayerSettings {
+        &self.players[id]
+    }
+
+    pub fn player_mut(&mut self, id: usize) -> &mut PlayerSettings {
+        &mut self.players[id]
+    }
 }
 
+/// Player whose settings the settings screen edits.
+#[derive(Resource, Default)]
+pub struct SettingsSelection(pub usize);
+
 #[derive(Component, Clone, Copy, PartialEq, Eq)]
 pub enum CrosshairKind {
     Vertical,
@@ -57,3 +720,781 @@ pub struct Crosshair {
     pub player_id: usize,
     pub kind: CrosshairKind,
 }
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum Action {
+    MoveForward,
+    MoveBack,
+    StrafeLeft,
+    StrafeRight,
+    Jump,
+    Fire,
+    Scoreboard,
+    Menu,
+    MenuUp,
+    MenuDown,
+    MenuLeft,
+    MenuRight,
+    MenuSelect,
+    NextPlayer,
+    SensitivityUp,
+    SensitivityDown,
+    CrosshairGrow,
+    CrosshairShrink,
+}
+
+impl Action {
+    /// Actions read from a player's own devices.
+    pub const PLAYER: [Action; 7] = [
+        Action::MoveForward,
+        Action::MoveBack,
+        Action::StrafeLeft,
+        Action::StrafeRight,
+        Action::Jump,
+        Action::Fire,
+        Action::Scoreboard,
+    ];
+    /// Actions read from any device, regardless of which player owns it.
+    pub const GLOBAL: [Action; 11] = [
+        Action::Menu,
+        Action::MenuUp,
+        Action::MenuDown,
+        Action::MenuLeft,
+        Action::MenuRight,
+        Action::MenuSelect,
+        Action::NextPlayer,
+        Action::SensitivityUp,
+        Action::SensitivityDown,
+        Action::CrosshairGrow,
+        Action::CrosshairShrink,
+    ];
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Gamepad(GamepadButton),
+}
+
+/// Action -> binding pairs; an action may have several bindings.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BindingTable(pub Vec<(Action, Binding)>);
+
+impl BindingTable {
+    pub fn bindings(&self, action: Action) -> impl Iterator<Item = Binding> + '_ {
+        self.0
+            .iter()
+            .filter(move |(a, _)| *a == action)
+            .map(|(_, b)| *b)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = KeyCode> + '_ {
+        self.0.iter().filter_map(|(_, b)| match b {
+            Binding::Key(k) => Some(*k),
+            _ => None,
+        })
+    }
+
+    /// Replaces every binding of `action` with `binding`.
+    pub fn rebind(&mut self, action: Action, binding: Binding) {
+        self.0.retain(|(a, _)| *a != action);
+        self.0.push((action, binding));
+    }
+}
+
+/// Binding tables, loaded from and saved to `BINDINGS_PATH`.
+#[derive(Resource, Clone, Debug, Serialize, Deserialize)]
+pub struct ActionBindings {
+    /// One table per virtual keyboard; a player uses the table they claimed.
+    pub keyboards: Vec<BindingTable>,
+    /// Used by whichever player claimed the mouse.
+    pub mouse: BindingTable,
+    /// Used by every player for their own gamepad.
+    pub gamepad: BindingTable,
+    /// Menu and settings actions, read from every device.
+    pub global: BindingTable,
+}
+
+impl Default for ActionBindings {
+    fn default() -> Self {
+        use Action::*;
+        let keyboard = |keys: [KeyCode; 6]| {
+            BindingTable(
+                [MoveForward, StrafeLeft, MoveBack, StrafeRight, Jump, Scoreboard]
+                    .into_iter()
+                    .zip(keys.map(Binding::Key))
+                    .collect(),
+            )
+        };
+        let keyboards = vec![
+            keyboard([
+                KeyCode::KeyW,
+                KeyCode::KeyA,
+                KeyCode::KeyS,
+                KeyCode::KeyD,
+                KeyCode::Space,
+                KeyCode::Tab,
+            ]),
+            keyboard([
+                KeyCode::ArrowUp,
+                KeyCode::ArrowLeft,
+                KeyCode::ArrowDown,
+                KeyCode::ArrowRight,
+                KeyCode::Numpad0,
+                KeyCode::ControlRight,
+            ]),
+            keyboard([
+                KeyCode::KeyI,
+                KeyCode::KeyJ,
+                KeyCode::KeyK,
+                KeyCode::KeyL,
+                KeyCode::ShiftRight,
+                KeyCode::KeyU,
+            ]),
+            keyboard([
+                KeyCode::Numpad8,
+                KeyCode::Numpad4,
+                KeyCode::Numpad5,
+                KeyCode::Numpad6,
+                KeyCode::NumpadEnter,
+                KeyCode::NumpadAdd,
+            ]),
+        ];
+        Self {
+            keyboards,
+            mouse: BindingTable(vec![(Fire, Binding::Mouse(MouseButton::Left))]),
+            gamepad: BindingTable(vec![
+                (Jump, Binding::Gamepad(GamepadButton::South)),
+                (Scoreboard, Binding::Gamepad(GamepadButton::Select)),
+                (Fire, Binding::Gamepad(GamepadButton::RightTrigger2)),
+                (Fire, Binding::Gamepad(GamepadButton::RightTrigger)),
+            ]),
+            global: BindingTable(vec![
+                (Menu, Binding::Key(KeyCode::Escape)),
+                (Menu, Binding::Gamepad(GamepadButton::Start)),
+                (MenuUp, Binding::Key(KeyCode::ArrowUp)),
+                (MenuUp, Binding::Gamepad(GamepadButton::DPadUp)),
+                (MenuDown, Binding::Key(KeyCode::ArrowDown)),
+                (MenuDown, Binding::Gamepad(GamepadButton::DPadDown)),
+                (MenuLeft, Binding::Key(KeyCode::ArrowLeft)),
+                (MenuLeft, Binding::Gamepad(GamepadButton::DPadLeft)),
+                (MenuRight, Binding::Key(KeyCode::ArrowRight)),
+                (MenuRight, Binding::Gamepad(GamepadButton::DPadRight)),
+                (MenuSelect, Binding::Key(KeyCode::Enter)),
+                (MenuSelect, Binding::Gamepad(GamepadButton::South)),
+                (NextPlayer, Binding::Key(KeyCode::Tab)),
+                (NextPlayer, Binding::Gamepad(GamepadButton::North)),
+                (SensitivityUp, Binding::Key(KeyCode::Equal)),
+                (SensitivityDown, Binding::Key(KeyCode::Minus)),
+                (CrosshairGrow, Binding::Key(KeyCode::BracketRight)),
+                (CrosshairShrink, Binding::Key(KeyCode::BracketLeft)),
+            ]),
+        }
+    }
+}
+
+/// Per-frame action state. A component on each player and a resource for the
+/// global (menu/settings) actions.
+#[derive(Component, Resource, Default)]
+pub struct ActionState {
+    pressed: HashSet<Action>,
+    just_pressed: HashSet<Action>,
+    pub movement: Vec2, // analog movement (x strafe, y forward)
+    pub look: Vec2,     // yaw/pitch delta in radians this frame
+}
+
+impl ActionState {
+    pub fn pressed(&self, action: Action) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.just_pressed.contains(&action)
+    }
+
+    pub fn set(&mut self, action: Action, pressed: bool, just_pressed: bool) {
+        if pressed {
+            self.pressed.insert(action);
+        }
+        if just_pressed {
+            self.just_pressed.insert(action);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.pressed.clear();
+        self.just_pressed.clear();
+        self.movement = Vec2::ZERO;
+        self.look = Vec2::ZERO;
+    }
+
+    /// Digital and analog movement combined, at most unit length.
+    pub fn move_axis(&self) -> Vec2 {
+        let digital = Vec2::new(
+            (self.pressed(Action::StrafeRight) as i8 - self.pressed(Action::StrafeLeft) as i8)
+                as f32,
+            (self.pressed(Action::MoveForward) as i8 - self.pressed(Action::MoveBack) as i8) as f32,
+        );
+        (digital.normalize_or_zero() + self.movement).clamp_length_max(1.0)
+    }
+}
+
+/// Rebinding screen shown in `AppMode::Bindings`.
+#[derive(Resource, Default)]
+pub struct RebindState {
+    pub table: usize,  // index into `RebindState::tables`
+    pub action: usize, // index into the selected table's actions
+    pub capturing: bool,
+}
+
+impl RebindState {
+    pub fn tables(bindings: &ActionBindings) -> Vec<String> {
+        let mut tables: Vec<String> = (1..=bindings.keyboards.len())
+            .map(|k| format!("Keyboard {k}"))
+            .collect();
+        tables.extend(["Mouse".into(), "Gamepad".into(), "Global".into()]);
+        tables
+    }
+
+    pub fn actions(&self, bindings: &ActionBindings) -> &'static [Action] {
+        if self.table == bindings.keyboards.len() + 2 {
+            &Action::GLOBAL
+        } else {
+            &Action::PLAYER
+        }
+    }
+
+    pub fn table<'a>(&self, bindings: &'a ActionBindings) -> &'a BindingTable {
+        let k = bindings.keyboards.len();
+        match self.table {
+            t if t < k => &bindings.keyboards[t],
+            t if t == k => &bindings.mouse,
+            t if t == k + 1 => &bindings.gamepad,
+            _ => &bindings.global,
+        }
+    }
+
+    pub fn table_mut<'a>(&self, bindings: &'a mut ActionBindings) -> &'a mut BindingTable {
+        let k = bindings.keyboards.len();
+        match self.table {
+            t if t < k => &mut bindings.keyboards[t],
+            t if t == k => &mut bindings.mouse,
+            t if t == k + 1 => &mut bindings.gamepad,
+            _ => &mut bindings.global,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct RebindText;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MenuItem {
+    Resume,
+    Settings,
+    PlayerCount,
+    Quit,
+    Player,
+    Sensitivity,
+    Crosshair,
+    GamepadSensitivity,
+    Bot,
+    Bindings,
+    Back,
+}
+
+/// A focusable row of a menu page; `index` gives the navigation order.
+#[derive(Component)]
+pub struct MenuEntry {
+    pub page: AppMode,
+    pub index: usize,
+    pub item: MenuItem,
+}
+
+/// Root node of a menu page, shown only while `State<AppMode>` is `page`.
+#[derive(Component)]
+pub struct MenuPage(pub AppMode);
+
+#[derive(Component)]
+pub struct MenuLabel(pub MenuItem);
+
+/// Slider track; clicking it sets the value from the cursor position.
+#[derive(Component)]
+pub struct SliderTrack(pub MenuItem);
+
+#[derive(Component)]
+pub struct SliderFill(pub MenuItem);
+
+/// Keyboard/gamepad focus on the current menu page.
+#[derive(Resource, Default)]
+pub struct MenuFocus(pub usize);
+
+#[derive(Clone, Default, Debug, Serialize, Deserialize)]
+pub struct PlayerScore {
+    pub kills: u32,
+    pub deaths: u32,
+}
+
+/// Kills and deaths per player id plus the match clock.
+#[derive(Resource, Clone, Debug, Serialize, Deserialize)]
+pub struct Scoreboard {
+    pub players: Vec<PlayerScore>,
+    /// Ticks of play this match; the clock counts these so it does not
+    /// depend on the frame rate.
+    pub ticks: u64,
+}
+
+impl Default for Scoreboard {
+    fn default() -> Self {
+        Self {
+            players: vec![PlayerScore::default(); MAX_PLAYER_COUNT],
+            ticks: 0,
+        }
+    }
+}
+
+impl Scoreboard {
+    /// Seconds of play this match, at `timestep` per tick.
+    pub fn elapsed(&self, timestep: std::time::Duration) -> f32 {
+        (self.ticks as f64 * timestep.as_secs_f64()) as f32
+    }
+
+    /// Player ids ordered by kills (desc), then deaths (asc), then id.
+    pub fn ranking(&self, player_count: usize) -> Vec<usize> {
+        let mut ids: Vec<usize> = (0..player_count.min(self.players.len())).collect();
+        ids.sort_by_key(|&id| {
+            let s = &self.players[id];
+            (std::cmp::Reverse(s.kills), s.deaths, id)
+        });
+        ids
+    }
+
+    /// The sole leader by kills, if there is one.
+    pub fn winner(&self, player_count: usize) -> Option<usize> {
+        let ranking = self.ranking(player_count);
+        match ranking.as_slice() {
+            [first, second, ..] if self.players[*first].kills == self.players[*second].kills => {
+                None
+            }
+            [first, ..] => Some(*first),
+            [] => None,
+        }
+    }
+}
+
+/// Final standings, printed and optionally written as JSON at match end.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MatchResults {
+    pub duration_secs: f32,
+    pub winner: Option<usize>,
+    pub players: Vec<PlayerResult>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PlayerResult {
+    pub id: usize,
+    pub kills: u32,
+    pub deaths: u32,
+}
+
+/// Scoreboard overlay inside one player's viewport.
+#[derive(Component)]
+pub struct ScoreboardText {
+    pub player_id: usize,
+}
+
+/// Where to write the final results as JSON, if anywhere.
+#[derive(Resource, Default)]
+pub struct ResultsPath(pub Option<std::path::PathBuf>);
+
+/// Datagram exchanged between a `--host` server and `--connect` clients, or
+/// between `--peer` rollback peers, one message per datagram.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum NetMessage {
+    /// Client asks for a player slot; resent until welcomed.
+    Hello { version: u32 },
+    Welcome {
+        player_id: usize,
+        tick_rate: f64,
+        /// The server's map, which the client is expected to run too.
+        map: String,
+    },
+    Rejected { reason: String },
+    /// Client inputs not yet acknowledged, oldest first, so a lost datagram
+    /// costs nothing.
+    Input(Vec<SentInput>),
+    Snapshot(Snapshot),
+    /// Client is leaving and frees its slot.
+    Bye,
+    /// A rollback peer's own inputs from `tick` on, resent until every peer
+    /// has them, and how many inputs of each player id it has received.
+    PeerInputs {
+        player: usize,
+        tick: u64,
+        inputs: Vec<TickInput>,
+        received: Vec<u64>,
+    },
+}
+
+/// One tick of a client's input as sent to the server.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SentInput {
+    /// Counts up from 1.
+    pub seq: u64,
+    pub input: TickInput,
+    /// Server tick of the snapshot on screen when the input was sampled;
+    /// its shot is checked against the world as it was then.
+    pub view_tick: u64,
+}
+
+/// Authoritative state of the match after one server tick.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub tick: u64,
+    /// Sequence number of the recipient's last input this tick applied.
+    pub ack: u64,
+    pub match_over: bool,
+    pub players: Vec<PlayerState>,
+    pub scoreboard: Scoreboard,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PlayerState {
+    pub id: usize,
+    pub translation: Vec3,
+    pub velocity: Vec3,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub health: f32,
+    pub dead: bool,
+}
+
+/// Socket of a server accepting remote players with `--host PORT`.
+#[derive(Resource)]
+pub struct NetServer {
+    pub socket: std::net::UdpSocket,
+}
+
+impl NetServer {
+    pub fn bind(addr: impl std::net::ToSocketAddrs) -> std::io::Result<Self> {
+        let socket = std::net::UdpSocket::bind(addr)?;
+        socket.set_nonblocking(true)?;
+        Ok(Self { socket })
+    }
+}
+
+/// A player slot played from another machine, on the server.
+#[derive(Component)]
+pub struct RemotePlayer {
+    pub addr: std::net::SocketAddr,
+    /// Received inputs waiting for their tick, one per tick, by sequence.
+    pub queue: std::collections::VecDeque<SentInput>,
+    /// Sequence number of the newest input received.
+    pub last_seq: u64,
+    /// Last input applied and its sequence number; repeated, without the
+    /// shot, when the next one is late.
+    pub input: TickInput,
+    pub ack: u64,
+    /// `Time<Real>` seconds when the client was last heard from.
+    pub last_heard: f64,
+}
+
+impl RemotePlayer {
+    /// Drops the oldest queued inputs beyond `NET_INPUT_QUEUE`, keeping their
+    /// shots, so a burst of late datagrams or a paused match cannot pile up
+    /// delay or memory.
+    pub fn trim_queue(&mut self) {
+        while self.queue.len() > NET_INPUT_QUEUE {
+            let dropped = self.queue.pop_front().unwrap();
+            if let Some(next) = self.queue.front_mut() {
+                next.input.fire |= dropped.input.fire;
+            }
+        }
+    }
+}
+
+/// Connection of a `--connect ADDR` client to its server.
+#[derive(Resource)]
+pub struct NetClient {
+    pub socket: std::net::UdpSocket,
+    /// Slot the server gave us, once welcomed.
+    pub player_id: Option<usize>,
+    pub seq: u64,
+    /// Server tick of the newest snapshot applied.
+    pub tick: u64,
+    /// `Time<Real>` seconds of the last datagram from the server, or of the
+    /// last `Hello` while still connecting.
+    pub last_heard: f64,
+    pub last_hello: Option<f64>,
+    pub timed_out: bool,
+}
+
+impl NetClient {
+    pub fn connect(server: impl std::net::ToSocketAddrs) -> std::io::Result<Self> {
+        let server = server
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| std::io::Error::other("no address to connect to"))?;
+        let local = if server.is_ipv4() {
+            "0.0.0.0:0"
+        } else {
+            "[::]:0"
+        };
+        let socket = std::net::UdpSocket::bind(local)?;
+        socket.connect(server)?;
+        socket.set_nonblocking(true)?;
+        Ok(Self {
+            socket,
+            player_id: None,
+            seq: 0,
+            tick: 0,
+            last_heard: 0.0,
+            last_hello: None,
+            timed_out: false,
+        })
+    }
+}
+
+/// Server tick whose world a remote player saw when aiming its latest input,
+/// on the server. Its shots hit targets where they were then.
+#[derive(Component, Clone, Copy, Debug, Deref)]
+pub struct ViewTick(pub u64);
+
+/// A player's recent positions by tick, oldest first, for rewinding shots to
+/// what a lagging shooter saw. Keyed like `Snapshot::tick`, the tick count
+/// after the tick that produced the position.
+#[derive(Component, Default)]
+pub struct PositionHistory(pub std::collections::VecDeque<(u64, Vec3)>);
+
+impl PositionHistory {
+    /// Position as of `tick`, clamped to the oldest one kept, or `None` when
+    /// `tick` is newer than anything kept.
+    pub fn at(&self, tick: u64) -> Option<Vec3> {
+        let &(newest, _) = self.0.back()?;
+        if tick > newest {
+            return None;
+        }
+        self.0
+            .iter()
+            .rev()
+            .find(|(t, _)| *t <= tick)
+            .or(self.0.front())
+            .map(|&(_, pos)| pos)
+    }
+}
+
+/// Player mirrored from server snapshots on a client; nothing local drives it.
+#[derive(Component)]
+pub struct Replicated;
+
+/// Our own player on a client, moved ahead of the server with our inputs and
+/// wound back onto each snapshot.
+#[derive(Component, Default)]
+pub struct Predicted {
+    /// Inputs sent but not yet applied by the server, oldest first.
+    pub pending: std::collections::VecDeque<SentInput>,
+    /// Server tick of the last snapshot reconciled with.
+    pub tick: u64,
+    /// Where the camera still shows us relative to the corrected position;
+    /// decays to zero so corrections glide instead of snapping.
+    pub correction: Vec3,
+}
+
+/// Player slot on a rollback peer that another peer plays.
+#[derive(Component)]
+pub struct PeerPlayer;
+
+/// What a rollback restores of one player.
+#[derive(Clone)]
+pub struct SavedPlayer {
+    pub entity: Entity,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub transform: Transform,
+    pub velocity: Vec3,
+    pub health: f32,
+    /// The respawn timer while dead.
+    pub dead: Option<Timer>,
+    pub history: std::collections::VecDeque<(u64, Vec3)>,
+}
+
+/// The simulation as it was before tick `tick` ran.
+#[derive(Clone)]
+pub struct SavedState {
+    pub tick: u64,
+    pub players: Vec<SavedPlayer>,
+    /// Scores and the match clock.
+    pub scoreboard: Scoreboard,
+    pub rng: StdRng,
+}
+
+/// A peer-to-peer match with `--peer ADDR`. Every peer simulates every
+/// player, guessing the inputs of the others until they arrive; when a guess
+/// turns out wrong the match is restored to that tick and simulated again.
+#[derive(Resource)]
+pub struct RollbackSession {
+    pub transport: Box<dyn PeerTransport>,
+    /// Our player id.
+    pub local: usize,
+    /// Ids of the players in the match, ours included.
+    pub players: Vec<usize>,
+    /// Every input of every player id so far by tick: ours as sampled, the
+    /// others' as received.
+    pub inputs: Vec<Vec<TickInput>>,
+    /// Inputs guessed for each other player, from their first tick not yet
+    /// received.
+    pub predicted: Vec<std::collections::VecDeque<TickInput>>,
+    /// How many of our inputs each player id has received.
+    pub acked: Vec<u64>,
+    /// States before each of the latest ticks, oldest first.
+    pub states: std::collections::VecDeque<SavedState>,
+    /// Set when the ticks before this one ended the match; no more ticks run
+    /// until every peer's inputs for them arrive and confirm it.
+    pub ending: Option<u64>,
+    /// Rollbacks so far.
+    pub rollbacks: u64,
+    /// `Time<Real>` seconds each player id was last heard from, once it has
+    /// been; a peer is waited for until then.
+    pub last_heard: Vec<Option<f64>>,
+}
+
+impl RollbackSession {
+    pub fn new(transport: impl PeerTransport + 'static, local: usize, players: Vec<usize>) -> Self {
+        Self {
+            transport: Box::new(transport),
+            local,
+            players,
+            inputs: vec![Vec::new(); MAX_PLAYER_COUNT],
+            predicted: vec![std::collections::VecDeque::new(); MAX_PLAYER_COUNT],
+            acked: vec![0; MAX_PLAYER_COUNT],
+            states: std::collections::VecDeque::new(),
+            ending: None,
+            rollbacks: 0,
+            last_heard: vec![None; MAX_PLAYER_COUNT],
+        }
+    }
+
+    fn remotes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.players.iter().copied().filter(|&p| p != self.local)
+    }
+
+    /// Whether `tick` is close enough to every peer's received inputs to
+    /// simulate.
+    pub fn can_advance(&self, tick: u64) -> bool {
+        self.ending.is_none()
+            && self
+                .remotes()
+                .all(|p| tick < self.inputs[p].len() as u64 + ROLLBACK_WINDOW)
+    }
+
+    /// Whether every peer's inputs for the ticks before `tick` have arrived,
+    /// so no rollback can change them any more.
+    pub fn confirmed(&self, tick: u64) -> bool {
+        self.remotes().all(|p| self.inputs[p].len() as u64 >= tick)
+    }
+
+    /// The input `player` plays on `tick`. Ours is `live` the first time the
+    /// tick runs; another player's is received or else guessed from its last
+    /// one, without the shot. Slots outside the match, or no longer in it,
+    /// stand still past what they sent.
+    pub fn input_for(&mut self, tick: u64, player: usize, live: TickInput) -> TickInput {
+        if !self.players.contains(&player) {
+            return self.inputs[player]
+                .get(tick as usize)
+                .copied()
+                .unwrap_or_default();
+        }
+        let tick = tick as usize;
+        let inputs = &mut self.inputs[player];
+        if player == self.local && tick == inputs.len() {
+            inputs.push(live);
+        }
+        if let Some(input) = inputs.get(tick) {
+            return *input;
+        }
+        let guess = inputs
+            .last()
+            .map_or_else(TickInput::default, |last| TickInput {
+                fire: false,
+                ..*last
+            });
+        let predicted = &mut self.predicted[player];
+        predicted.truncate(tick - inputs.len());
+        predicted.push_back(guess);
+        guess
+    }
+
+    /// Takes in the inputs that arrived by `now`, in `Time<Real>` seconds.
+    /// Returns the earliest tick simulated with a wrong guess, which has to
+    /// run again.
+    pub fn receive(&mut self, now: f64) -> Option<u64> {
+        let mut rollback: Option<u64> = None;
+        for message in self.transport.receive() {
+            let NetMessage::PeerInputs {
+                player,
+                tick,
+                inputs,
+                received,
+            } = message
+            else {
+                continue;
+            };
+            if player == self.local || !self.players.contains(&player) {
+                continue;
+            }
+            self.last_heard[player] = Some(now);
+            if let Some(&n) = received.get(self.local) {
+                self.acked[player] = self.acked[player].max(n);
+            }
+            for (tick, input) in (tick..).zip(inputs) {
+                // Only the next one; anything after a gap is resent later
+                if tick != self.inputs[player].len() as u64 {
+                    continue;
+                }
+                let input = input.sanitized();
+                self.inputs[player].push(input);
+                if let Some(guess) = self.predicted[player].pop_front()
+                    && guess != input
+                {
+                    rollback = Some(rollback.map_or(tick, |r| r.min(tick)));
+                }
+            }
+        }
+        rollback
+    }
+
+    /// Leaves out of the match the peers not heard from for `NET_TIMEOUT`
+    /// seconds by `now`, so it goes on without them, and returns their ids.
+    /// Their players stand still from the first tick not received.
+    pub fn drop_silent(&mut self, now: f64) -> Vec<usize> {
+        let silent: Vec<usize> = self
+            .remotes()
+            .filter(|&p| self.last_heard[p].is_some_and(|t| now - t > NET_TIMEOUT))
+            .collect();
+        for &p in &silent {
+            self.players.retain(|&q| q != p);
+            self.predicted[p].clear();
+        }
+        silent
+    }
+
+    /// Sends our inputs some peer still lacks, and what we have received.
+    pub fn send(&mut self) {
+        let inputs = &self.inputs[self.local];
+        let from = self
+            .remotes()
+            .map(|p| self.acked[p] as usize)
+            .min()
+            .unwrap_or(inputs.len())
+            .min(inputs.len());
+        let to = inputs.len().min(from + ROLLBACK_SEND_LIMIT);
+        let message = NetMessage::PeerInputs {
+            player: self.local,
+            tick: from as u64,
+            inputs: inputs[from..to].to_vec(),
+            received: self.inputs.iter().map(|i| i.len() as u64).collect(),
+        };
+        self.transport.send(&message);
+    }
+}
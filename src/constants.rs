@@ -4,8 +4,55 @@ pub const WINDOW_WIDTH: f32 = 1920.0;
 pub const WINDOW_HEIGHT: f32 = 1080.0;
 pub const CROSS_WORLD_HALF: f32 = 0.15; // half length of crosshair arms in world units
 
-// Physics tuning
-pub const DRAG_RATE: f32 = 6.0;
-pub const MOVE_MULT: f32 = 60.0;
-pub const GRAVITY: f32 = 25.0;
-pub const JUMP_VELOCITY: f32 = 8.4375;
+// Physics; the tuning values live in `PhysicsConfig`
+// Contacts whose normal points up more than this are ground to stand on
+pub const GROUND_NORMAL_Y: f32 = 0.7;
+// Cameras and shots start this far above the head centre
+pub const EYE_HEIGHT: f32 = 0.5;
+// Furthest a player can look up or down, in radians
+pub const MAX_PITCH: f32 = 1.6;
+// Simulation ticks per second, overridable with `--tick-rate`
+pub const TICK_RATE: f64 = 60.0;
+
+// Config files, relative to the working directory
+pub const BINDINGS_PATH: &str = "config/bindings.ron";
+pub const SETTINGS_PATH: &str = "config/settings.ron";
+pub const SETTINGS_VERSION: u32 = 1;
+// Largest gamepad dead zone a settings file may ask for; some range has to be
+// left to rescale stick input into
+pub const MAX_DEAD_ZONE: f32 = 0.95;
+
+// Assets, relative to the `assets` directory; the map is overridable with `--map`
+pub const DEFAULT_MAP_PATH: &str = "maps/default.map.ron";
+pub const PHYSICS_CONFIG_PATH: &str = "physics/default.physics.ron";
+
+// Format of the demo files written by `--record`
+pub const DEMO_VERSION: u32 = 3;
+
+// Networking for `--host` and `--connect`
+pub const NET_PROTOCOL_VERSION: u32 = 1;
+// Seconds of silence before a client or rollback peer is dropped, or a server
+// given up on
+pub const NET_TIMEOUT: f64 = 5.0;
+// Seconds between `Hello`s while connecting
+pub const NET_HELLO_INTERVAL: f64 = 0.5;
+// Unacknowledged inputs resent with every input datagram
+pub const NET_INPUT_REDUNDANCY: usize = 8;
+// Inputs a server queues per client before dropping the oldest, bounding the
+// delay a burst of late datagrams can add
+pub const NET_INPUT_QUEUE: usize = 8;
+// Furthest back in seconds a match may check lagging shooters' shots; every
+// player keeps this much position history
+pub const MAX_REWIND: f32 = 1.0;
+// Client-side prediction: corrections decay at this rate per second, and ones
+// longer than the snap distance (respawns) are not smoothed
+pub const CORRECTION_RATE: f32 = 10.0;
+pub const CORRECTION_SNAP_DISTANCE: f32 = 2.0;
+// Unacknowledged inputs a client keeps before dropping the oldest
+pub const PREDICTION_MAX_PENDING: usize = 120;
+
+// Rollback netcode for `--peer`: ticks simulated past a peer's last received
+// input before waiting for it, which also bounds how far a rollback rewinds
+pub const ROLLBACK_WINDOW: u64 = 8;
+// Local inputs sent in one message, oldest unacknowledged first
+pub const ROLLBACK_SEND_LIMIT: usize = 64;
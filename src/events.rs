@@ -0,0 +1,48 @@
+use bevy::prelude::*;
+
+use crate::components::NetMessage;
+
+/// Which of the two player spheres a shot struck.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HitZone {
+    Head,
+    Feet,
+}
+
+#[derive(Event, Clone, Debug)]
+pub struct ShotFired {
+    pub shooter: usize,
+    pub origin: Vec3,
+    pub direction: Vec3,
+    pub tick: u64, // SimTick it happened on; a rollback may emit it again
+}
+
+#[derive(Event, Clone, Debug)]
+pub struct PlayerHit {
+    pub shooter: usize,
+    pub target: usize,
+    pub location: Vec3, // where the ray entered the struck sphere
+    pub zone: HitZone,
+    pub damage: f32,
+    pub tick: u64,
+}
+
+#[derive(Event, Clone, Debug)]
+pub struct PlayerKilled {
+    pub shooter: usize,
+    pub target: usize,
+    pub location: Vec3,
+    pub zone: HitZone,
+    pub tick: u64,
+}
+
+#[derive(Event, Clone, Debug)]
+pub struct PlayerRespawned {
+    pub player: usize,
+    pub location: Vec3,
+    pub tick: u64,
+}
+
+/// A datagram a `--connect` client received from its server.
+#[derive(Event, Clone, Debug)]
+pub struct ServerMessage(pub NetMessage);
@@ -0,0 +1,180 @@
+//! Windowless runner for servers, soak tests and automated matches: the
+//! simulation only, driven by an `InputScript` for a fixed number of ticks.
+
+use std::path::Path;
+use std::time::Duration;
+
+use bevy::app::ScheduleRunnerPlugin;
+use bevy::log::LogPlugin;
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+
+use crate::components::{
+    AppMode, InputScript, MapDef, MapPath, MatchRng, PhysicsConfig, PlayerCount, SimTick,
+    SpawnPoints, TickInput,
+};
+use crate::constants::{
+    DEFAULT_MAP_PATH, MAX_PLAYER_COUNT, PHYSICS_CONFIG_PATH, SETTINGS_PATH, TICK_RATE,
+};
+use crate::helpers::{cli_flag, cli_value, load_map_file, load_settings, parse_physics_config};
+use crate::plugins::{ReplayPlugin, SimulationPlugin};
+use crate::systems;
+
+pub struct HeadlessConfig {
+    /// Stop after this many ticks, or earlier when the match ends.
+    pub ticks: u64,
+    /// Pace ticks in real time like a server instead of running flat out.
+    pub realtime: bool,
+    pub tick_rate: f64,
+    pub seed: Option<u64>,
+    pub map: MapDef,
+    pub physics: PhysicsConfig,
+}
+
+impl Default for HeadlessConfig {
+    fn default() -> Self {
+        Self {
+            ticks: 3600,
+            realtime: false,
+            tick_rate: TICK_RATE,
+            seed: None,
+            map: MapDef::default(),
+            physics: PhysicsConfig::default(),
+        }
+    }
+}
+
+#[derive(Resource)]
+struct TickLimit(u64);
+
+/// Builds a windowless app running the simulation for every player with
+/// inputs from `script`. Call `App::run`, or `App::update` until
+/// `App::should_exit` in tests.
+pub fn headless_app(
+    config: HeadlessConfig,
+    script: impl Fn(u64, usize) -> TickInput + Send + Sync + 'static,
+) -> App {
+    let tick = Duration::from_secs_f64(1.0 / config.tick_rate);
+    let wait = if config.realtime {
+        tick
+    } else {
+        Duration::ZERO
+    };
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins.set(ScheduleRunnerPlugin::run_loop(wait)),
+        StatesPlugin,
+        SimulationPlugin,
+    ))
+    .insert_resource(Time::<Fixed>::from_duration(tick));
+    if !config.realtime {
+        // Every update advances the clock by exactly one tick
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(tick));
+    }
+    if let Some(seed) = config.seed {
+        app.insert_resource(MatchRng::seeded(seed));
+    }
+    app.insert_resource(config.map.arena())
+        .insert_resource(SpawnPoints(config.map.spawn_points.clone()))
+        .insert_resource(config.map.clone())
+        .insert_resource(config.physics)
+        .insert_resource(PlayerCount(MAX_PLAYER_COUNT))
+        .insert_resource(InputScript(Box::new(script)))
+        .insert_resource(TickLimit(config.ticks))
+        .add_systems(Last, exit_when_done.before(systems::write_demo));
+    app
+}
+
+fn exit_when_done(
+    tick: Res<SimTick>,
+    limit: Res<TickLimit>,
+    mode: Res<State<AppMode>>,
+    mut exit: EventWriter<AppExit>,
+) {
+    if tick.0 >= limit.0 || *mode.get() == AppMode::MatchOver {
+        exit.write(AppExit::Success);
+    }
+}
+
+/// Every player runs in a slow circle and fires twice a second.
+pub fn wander_script(tick: u64, player: usize) -> TickInput {
+    let t = tick as f32 / 60.0;
+    TickInput {
+        movement: Vec2::new(0.0, -1.0),
+        jump: tick % 90 == player as u64 * 20,
+        fire: tick.is_multiple_of(30),
+        yaw: t * 0.5 + player as f32 * std::f32::consts::FRAC_PI_2,
+        pitch: 0.0,
+    }
+}
+
+/// `--headless [--ticks N] [--realtime] [--bots] [--replay DEMO]`, plus the
+/// usual match and network flags. A replay takes its map, physics and length
+/// from the demo. A `--host` server runs until stopped, with its own slots
+/// idle. `--bots` hands every slot but the first to the bots in the settings.
+pub fn run_headless() {
+    let demo = crate::replay_demo();
+    let map_path = match &demo {
+        Some(demo) => demo.map.clone(),
+        None => cli_value("--map").unwrap_or_else(|| DEFAULT_MAP_PATH.into()),
+    };
+    // A replay brings its own map and physics
+    let map = match demo.as_ref().and_then(|d| d.map_change(0)) {
+        Some(map) => map.clone(),
+        None => load_map_file(Path::new("assets").join(&map_path)).unwrap_or_else(|e| {
+            eprintln!("{map_path}: {e}");
+            std::process::exit(1);
+        }),
+    };
+    let physics = demo
+        .as_ref()
+        .and_then(|d| d.physics_change(0))
+        .unwrap_or_else(load_physics_file);
+    let hosting = cli_value("--host").is_some();
+    let networked = hosting || cli_value("--connect").is_some() || cli_value("--peer").is_some();
+    let ticks = match &demo {
+        Some(demo) => demo.ticks.len() as u64,
+        None => cli_value("--ticks")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(if hosting {
+                u64::MAX
+            } else {
+                HeadlessConfig::default().ticks
+            }),
+    };
+    let config = HeadlessConfig {
+        ticks,
+        // Peers pace their ticks by the clock
+        realtime: cli_flag("--realtime") || networked,
+        tick_rate: demo.as_ref().map_or_else(crate::tick_rate, |d| d.tick_rate),
+        map,
+        physics,
+        ..default()
+    };
+    let mut app = if hosting {
+        headless_app(config, |_, _| TickInput::default())
+    } else {
+        headless_app(config, wander_script)
+    };
+    app.add_plugins(LogPlugin::default())
+        .insert_resource(MapPath(map_path));
+    if cli_flag("--bots") {
+        app.insert_resource(load_settings(SETTINGS_PATH));
+    }
+    crate::apply_match_flags(&mut app);
+    if let Some(demo) = demo {
+        app.add_plugins(ReplayPlugin(demo));
+    }
+    app.run();
+}
+
+fn load_physics_file() -> PhysicsConfig {
+    std::fs::read_to_string(Path::new("assets").join(PHYSICS_CONFIG_PATH))
+        .map_err(|e| e.to_string())
+        .and_then(|text| parse_physics_config(&text).map_err(|e| e.to_string()))
+        .unwrap_or_else(|e| {
+            eprintln!("{PHYSICS_CONFIG_PATH}: {e}; using default physics");
+            PhysicsConfig::default()
+        })
+}
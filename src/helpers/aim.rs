@@ -0,0 +1,22 @@
+use bevy::prelude::*;
+
+use crate::constants::EYE_HEIGHT;
+
+/// Unit vector for a yaw and pitch; yaw 0 faces -Z.
+pub fn look_direction(yaw: f32, pitch: f32) -> Vec3 {
+    let (sin_yaw, cos_yaw) = yaw.sin_cos();
+    let (sin_pitch, cos_pitch) = pitch.sin_cos();
+    Vec3::new(-sin_yaw * cos_pitch, sin_pitch, -cos_yaw * cos_pitch)
+}
+
+/// Yaw and pitch looking along `dir`; the inverse of `look_direction`.
+pub fn look_angles(dir: Vec3) -> (f32, f32) {
+    let yaw = (-dir.x).atan2(-dir.z);
+    let pitch = dir.y.atan2(Vec2::new(dir.x, dir.z).length());
+    (yaw, pitch)
+}
+
+/// Where a player at `translation` sees and shoots from.
+pub fn eye_position(translation: Vec3) -> Vec3 {
+    translation + Vec3::Y * EYE_HEIGHT
+}
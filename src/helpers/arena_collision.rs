@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+
+use crate::components::Obstacle;
+
+/// Moves a player capsule (head sphere at `pos`, feet sphere `height - radius`
+/// below) out of every obstacle it overlaps. Returns the contact normals.
+pub fn push_out_of_obstacles(
+    obstacles: &[Obstacle],
+    pos: &mut Vec3,
+    radius: f32,
+    height: f32,
+) -> Vec<Vec3> {
+    let mut normals = Vec::new();
+    for obstacle in obstacles {
+        if let Some((normal, depth)) = capsule_penetration(obstacle, *pos, radius, height) {
+            *pos += normal * depth;
+            normals.push(normal);
+        }
+    }
+    normals
+}
+
+pub fn capsule_overlaps(obstacles: &[Obstacle], pos: Vec3, radius: f32, height: f32) -> bool {
+    obstacles
+        .iter()
+        .any(|o| capsule_penetration(o, pos, radius, height).is_some())
+}
+
+fn capsule_penetration(o: &Obstacle, pos: Vec3, radius: f32, height: f32) -> Option<(Vec3, f32)> {
+    let low = pos.y + radius - height;
+    let dy = if low > o.max.y {
+        low - o.max.y
+    } else if pos.y < o.min.y {
+        pos.y - o.min.y
+    } else {
+        0.0
+    };
+    let delta = Vec3::new(
+        pos.x - pos.x.clamp(o.min.x, o.max.x),
+        dy,
+        pos.z - pos.z.clamp(o.min.z, o.max.z),
+    );
+    let dist = delta.length();
+    if dist > 1e-6 {
+        return (dist < radius).then(|| (delta / dist, radius - dist));
+    }
+    // The capsule's core is inside the box: leave by the shallowest face
+    [
+        (Vec3::X, o.max.x - pos.x),
+        (Vec3::NEG_X, pos.x - o.min.x),
+        (Vec3::Z, o.max.z - pos.z),
+        (Vec3::NEG_Z, pos.z - o.min.z),
+        (Vec3::Y, o.max.y - low),
+        (Vec3::NEG_Y, pos.y - o.min.y),
+    ]
+    .into_iter()
+    .map(|(normal, depth)| (normal, depth + radius))
+    .min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// Distance along the ray to the nearest obstacle it enters, if any.
+pub fn ray_obstacle_distance(obstacles: &[Obstacle], origin: Vec3, dir: Vec3) -> Option<f32> {
+    obstacles
+        .iter()
+        .filter_map(|o| {
+            // Slab test
+            let inv = dir.recip();
+            let t0 = (o.min - origin) * inv;
+            let t1 = (o.max - origin) * inv;
+            let near = t0.min(t1).max_element();
+            let far = t0.max(t1).min_element();
+            (far >= near.max(0.0)).then_some(near.max(0.0))
+        })
+        .min_by(f32::total_cmp)
+}
+
+/// Whether no obstacle lies between `from` and `to`.
+pub fn line_of_sight(obstacles: &[Obstacle], from: Vec3, to: Vec3) -> bool {
+    let offset = to - from;
+    ray_obstacle_distance(obstacles, from, offset.normalize_or_zero())
+        .is_none_or(|wall| wall > offset.length())
+}
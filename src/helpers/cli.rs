@@ -0,0 +1,11 @@
+/// Whether `--flag` was passed on the command line.
+pub fn cli_flag(flag: &str) -> bool {
+    std::env::args().any(|a| a == flag)
+}
+
+/// Value following `--flag` on the command line, e.g. `--results out.json`.
+pub fn cli_value(flag: &str) -> Option<String> {
+    let mut args = std::env::args().skip_while(|a| a != flag);
+    args.next()?;
+    args.next()
+}
@@ -0,0 +1,28 @@
+use std::fs;
+use std::path::Path;
+
+use bevy::prelude::*;
+use serde::{Serialize, de::DeserializeOwned};
+
+/// Loads a RON config from `path`, falling back to `T::default()` when the
+/// file is missing or malformed.
+pub fn load_config<T: DeserializeOwned + Default>(path: impl AsRef<Path>) -> T {
+    let path = path.as_ref();
+    match fs::read_to_string(path) {
+        Ok(text) => ron::from_str(&text).unwrap_or_else(|e| {
+            warn!("ignoring malformed config file {}: {e}", path.display());
+            T::default()
+        }),
+        Err(_) => T::default(),
+    }
+}
+
+pub fn save_config<T: Serialize>(path: impl AsRef<Path>, value: &T) -> std::io::Result<()> {
+    let path = path.as_ref();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let text = ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())
+        .map_err(std::io::Error::other)?;
+    fs::write(path, text)
+}
@@ -0,0 +1,16 @@
+use bevy::prelude::*;
+
+use crate::constants::MAX_DEAD_ZONE;
+
+/// Radial dead zone: sticks inside `dead_zone` read as zero and the remaining
+/// range is rescaled so full deflection still reaches 1.0. The dead zone is
+/// capped at `MAX_DEAD_ZONE`.
+pub fn apply_dead_zone(stick: Vec2, dead_zone: f32) -> Vec2 {
+    let dead_zone = dead_zone.clamp(0.0, MAX_DEAD_ZONE);
+    let len = stick.length();
+    if len <= dead_zone {
+        return Vec2::ZERO;
+    }
+    let scaled = ((len - dead_zone) / (1.0 - dead_zone)).min(1.0);
+    stick / len * scaled
+}
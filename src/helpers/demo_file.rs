@@ -0,0 +1,60 @@
+use std::fmt;
+use std::path::Path;
+
+use crate::components::Demo;
+use crate::constants::DEMO_VERSION;
+
+#[derive(Debug)]
+pub enum DemoError {
+    Io(std::io::Error),
+    Parse(serde_json::Error),
+    /// Recorded by an incompatible build.
+    Version(u32),
+}
+
+impl fmt::Display for DemoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DemoError::Io(e) => write!(f, "cannot access demo: {e}"),
+            DemoError::Parse(e) => write!(f, "malformed demo: {e}"),
+            DemoError::Version(v) => {
+                write!(
+                    f,
+                    "demo version {v} is not supported (expected {DEMO_VERSION})"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for DemoError {}
+
+impl From<std::io::Error> for DemoError {
+    fn from(e: std::io::Error) -> Self {
+        DemoError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for DemoError {
+    fn from(e: serde_json::Error) -> Self {
+        DemoError::Parse(e)
+    }
+}
+
+pub fn load_demo(path: impl AsRef<Path>) -> Result<Demo, DemoError> {
+    let demo: Demo = serde_json::from_slice(&std::fs::read(path)?)?;
+    if demo.version != DEMO_VERSION {
+        return Err(DemoError::Version(demo.version));
+    }
+    Ok(demo)
+}
+
+/// Writes compact JSON, since demos hold an entry per player per tick.
+pub fn save_demo(path: impl AsRef<Path>, demo: &Demo) -> Result<(), DemoError> {
+    let path = path.as_ref();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, serde_json::to_vec(demo)?)?;
+    Ok(())
+}
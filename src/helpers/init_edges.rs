@@ -2,8 +2,10 @@ use bevy::prelude::*;
 
 use crate::components::Edges;
 
-pub fn init_edges(scale: i32) -> Edges {
-    let r = scale as f32;
+/// Wireframe of the arena cube plus `grid_lines` floor lines along each axis.
+pub fn init_edges(half_extent: f32, grid_lines: usize) -> Edges {
+    let r = half_extent;
+    let spacing = 2.0 * r / grid_lines.max(1) as f32;
 
     let map = [
         0, 1, 1, 3, 3, 2, 2, 0, // bottom
@@ -11,7 +13,7 @@ pub fn init_edges(scale: i32) -> Edges {
         6, 2, 3, 7, 0, 4, 5, 1, // verticals
     ];
 
-    let mut edges: Vec<(Vec3, Vec3)> = Vec::with_capacity((12 + scale as usize * 2) as usize);
+    let mut edges: Vec<(Vec3, Vec3)> = Vec::with_capacity(12 + grid_lines * 4);
 
     for i in 0..12 {
         let mut a = Vec3::ZERO;
@@ -23,8 +25,8 @@ pub fn init_edges(scale: i32) -> Edges {
         edges.push((a, b));
     }
 
-    for i in 0..scale as usize {
-        let d = (i as f32) * 2.0;
+    for i in 0..grid_lines {
+        let d = (i as f32) * spacing;
         for j in 0..2 {
             // wall 1
             let x = if j != 0 { r } else { -r };
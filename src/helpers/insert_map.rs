@@ -0,0 +1,14 @@
+use bevy::prelude::*;
+
+use crate::components::{MapDef, SpawnPoints};
+use crate::helpers::init_edges;
+
+/// Puts `map` in play: the arena, spawn points, wireframe and colors, plus
+/// the map itself.
+pub fn insert_map(commands: &mut Commands, map: &MapDef) {
+    commands.insert_resource(map.arena());
+    commands.insert_resource(SpawnPoints(map.spawn_points.clone()));
+    commands.insert_resource(init_edges(map.half_extent, map.grid_lines));
+    commands.insert_resource(map.colors.clone());
+    commands.insert_resource(map.clone());
+}
@@ -0,0 +1,124 @@
+use std::fmt;
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use bevy::prelude::*;
+
+use crate::components::MapDef;
+
+#[derive(Debug)]
+pub enum MapError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+    /// `entry` names the offending field, e.g. `obstacles[3]`.
+    Invalid {
+        entry: String,
+        reason: String,
+    },
+}
+
+impl fmt::Display for MapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MapError::Io(e) => write!(f, "cannot read map: {e}"),
+            MapError::Parse(e) => write!(f, "malformed map at {}: {}", e.position, e.code),
+            MapError::Invalid { entry, reason } => write!(f, "invalid map entry {entry}: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for MapError {}
+
+impl From<std::io::Error> for MapError {
+    fn from(e: std::io::Error) -> Self {
+        MapError::Io(e)
+    }
+}
+
+fn invalid(entry: impl Into<String>, reason: impl Into<String>) -> MapError {
+    MapError::Invalid {
+        entry: entry.into(),
+        reason: reason.into(),
+    }
+}
+
+/// Reads and validates a map outside the asset server, e.g. when headless.
+pub fn load_map_file(path: impl AsRef<std::path::Path>) -> Result<MapDef, MapError> {
+    parse_map(&std::fs::read_to_string(path)?)
+}
+
+/// Parses and validates a map file.
+pub fn parse_map(text: &str) -> Result<MapDef, MapError> {
+    let mut map: MapDef = ron::from_str(text).map_err(MapError::Parse)?;
+    validate_map(&map)?;
+    if map.grid_lines == 0 {
+        map.grid_lines = MapDef::grid_lines_for(map.half_extent);
+    }
+    Ok(map)
+}
+
+pub fn validate_map(map: &MapDef) -> Result<(), MapError> {
+    let r = map.half_extent;
+    if !(r.is_finite() && r > 0.0) {
+        return Err(invalid("half_extent", format!("must be positive, got {r}")));
+    }
+    let inside = |p: Vec3| p.abs().cmple(Vec3::splat(r)).all();
+    for (i, o) in map.obstacles.iter().enumerate() {
+        if !o.min.cmplt(o.max).all() {
+            return Err(invalid(
+                format!("obstacles[{i}]"),
+                format!("min {} must be below max {} on every axis", o.min, o.max),
+            ));
+        }
+        if !inside(o.min) || !inside(o.max) {
+            return Err(invalid(
+                format!("obstacles[{i}]"),
+                format!("extends outside the arena (half_extent {r})"),
+            ));
+        }
+    }
+    for (i, &p) in map.spawn_points.iter().enumerate() {
+        if !inside(p) {
+            return Err(invalid(
+                format!("spawn_points[{i}]"),
+                format!("{p} lies outside the arena (half_extent {r})"),
+            ));
+        }
+        if let Some(j) = map
+            .obstacles
+            .iter()
+            .position(|o| p.cmpgt(o.min).all() && p.cmplt(o.max).all())
+        {
+            return Err(invalid(
+                format!("spawn_points[{i}]"),
+                format!("{p} is inside obstacles[{j}]"),
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[derive(Default)]
+pub struct MapLoader;
+
+impl AssetLoader for MapLoader {
+    type Asset = MapDef;
+    type Settings = ();
+    type Error = MapError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<MapDef, MapError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let text = String::from_utf8(bytes).map_err(std::io::Error::other)?;
+        parse_map(&text)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["map.ron"]
+    }
+}
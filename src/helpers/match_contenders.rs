@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+
+use crate::components::{Bot, PeerPlayer, Player, RemotePlayer, Replicated};
+
+/// Slots played by something other than a local device.
+pub type OthersFilter = Or<(
+    With<Bot>,
+    With<RemotePlayer>,
+    With<Replicated>,
+    With<PeerPlayer>,
+)>;
+
+/// Player slots taking part in the match: the `player_count` local players,
+/// plus bots and remote players in slots nobody joined here. Pass the players
+/// matching `OthersFilter`.
+pub fn match_contenders<'a>(
+    player_count: usize,
+    others: impl IntoIterator<Item = &'a Player>,
+) -> usize {
+    others
+        .into_iter()
+        .fold(player_count, |n, p| n.max(p.id + 1))
+}
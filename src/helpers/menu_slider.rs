@@ -0,0 +1,76 @@
+use crate::components::{MenuItem, PlayerSettings};
+
+/// (min, max, step) of a slider menu item, `None` for non-slider items.
+pub fn slider_range(item: MenuItem) -> Option<(f32, f32, f32)> {
+    match item {
+        MenuItem::Sensitivity => Some((0.0001, 0.02, 0.0005)),
+        MenuItem::Crosshair => Some((0.02, 1.0, 0.02)),
+        MenuItem::GamepadSensitivity => Some((0.5, 10.0, 0.5)),
+        _ => None,
+    }
+}
+
+fn slider_field(settings: &mut PlayerSettings, item: MenuItem) -> Option<&mut f32> {
+    match item {
+        MenuItem::Sensitivity => Some(&mut settings.sensitivity),
+        MenuItem::Crosshair => Some(&mut settings.crosshair_half),
+        MenuItem::GamepadSensitivity => Some(&mut settings.gamepad_sensitivity),
+        _ => None,
+    }
+}
+
+/// Slider position in 0..=1.
+pub fn slider_fraction(settings: &PlayerSettings, item: MenuItem) -> f32 {
+    let value = match item {
+        MenuItem::Sensitivity => settings.sensitivity,
+        MenuItem::Crosshair => settings.crosshair_half,
+        MenuItem::GamepadSensitivity => settings.gamepad_sensitivity,
+        _ => return 0.0,
+    };
+    slider_range(item).map_or(0.0, |(min, max, _)| {
+        ((value - min) / (max - min)).clamp(0.0, 1.0)
+    })
+}
+
+/// Moves a slider by whole steps, clamped to its range.
+pub fn step_slider(settings: &mut PlayerSettings, item: MenuItem, steps: i32) {
+    if let (Some((min, max, step)), Some(value)) =
+        (slider_range(item), slider_field(settings, item))
+    {
+        *value = (*value + step * steps as f32).clamp(min, max);
+    }
+}
+
+/// Sets a slider from a 0..=1 position, e.g. where the track was clicked. A
+/// position that is not a number leaves it as it is.
+pub fn set_slider(settings: &mut PlayerSettings, item: MenuItem, fraction: f32) {
+    if fraction.is_nan() {
+        return;
+    }
+    if let (Some((min, max, _)), Some(value)) = (slider_range(item), slider_field(settings, item)) {
+        *value = min + (max - min) * fraction.clamp(0.0, 1.0);
+    }
+}
+
+/// Pulls every slider of settings read from a file back into its range, and
+/// resets those that are not numbers.
+pub fn clamp_sliders(settings: &mut PlayerSettings) {
+    let mut defaults = PlayerSettings::default();
+    for item in [
+        MenuItem::Sensitivity,
+        MenuItem::Crosshair,
+        MenuItem::GamepadSensitivity,
+    ] {
+        if let (Some((min, max, _)), Some(value), Some(&mut default)) = (
+            slider_range(item),
+            slider_field(settings, item),
+            slider_field(&mut defaults, item),
+        ) {
+            *value = if value.is_nan() {
+                default
+            } else {
+                value.clamp(min, max)
+            };
+        }
+    }
+}
@@ -1,3 +1,39 @@
+mod aim;
+mod arena_collision;
+mod cli;
+mod config_file;
+mod dead_zone;
+mod demo_file;
 mod init_edges;
+mod insert_map;
+mod map_file;
+mod match_contenders;
+mod menu_slider;
+mod net_codec;
+mod peer_transport;
+mod physics_file;
+mod player_collision;
+mod player_movement;
+mod settings_file;
+mod spawn_point;
 
+pub use aim::{eye_position, look_angles, look_direction};
+pub use arena_collision::{
+    capsule_overlaps, line_of_sight, push_out_of_obstacles, ray_obstacle_distance,
+};
+pub use cli::{cli_flag, cli_value};
+pub use config_file::{load_config, save_config};
+pub use dead_zone::apply_dead_zone;
+pub use demo_file::{DemoError, load_demo, save_demo};
 pub use init_edges::init_edges;
+pub use insert_map::insert_map;
+pub use map_file::{MapError, MapLoader, load_map_file, parse_map, validate_map};
+pub use match_contenders::{OthersFilter, match_contenders};
+pub use menu_slider::{clamp_sliders, set_slider, slider_fraction, slider_range, step_slider};
+pub use net_codec::{decode_message, encode_message, receive_messages, send_message};
+pub use peer_transport::{LoopbackTransport, PeerTransport, UdpTransport};
+pub use physics_file::{PhysicsConfigError, PhysicsConfigLoader, parse_physics_config};
+pub use player_collision::{collide_players, collide_with_players, confine_player};
+pub use player_movement::step_player;
+pub use settings_file::load_settings;
+pub use spawn_point::choose_spawn_point;
@@ -0,0 +1,57 @@
+use std::io::ErrorKind;
+use std::net::{SocketAddr, UdpSocket};
+
+use bevy::log::warn;
+
+use crate::components::NetMessage;
+
+/// Large enough for a snapshot of every player.
+const MAX_DATAGRAM: usize = 16 * 1024;
+
+pub fn encode_message(message: &NetMessage) -> Vec<u8> {
+    serde_json::to_vec(message).expect("network messages always serialize")
+}
+
+pub fn decode_message(bytes: &[u8]) -> Option<NetMessage> {
+    serde_json::from_slice(bytes).ok()
+}
+
+/// Sends to `addr`, or to the connected peer when `addr` is `None`. Lost
+/// datagrams are normal, so failures are only logged.
+pub fn send_message(socket: &UdpSocket, addr: Option<SocketAddr>, message: &NetMessage) {
+    let bytes = encode_message(message);
+    let sent = match addr {
+        Some(addr) => socket.send_to(&bytes, addr),
+        None => socket.send(&bytes),
+    };
+    if let Err(e) = sent
+        && e.kind() != ErrorKind::WouldBlock
+        && e.kind() != ErrorKind::ConnectionRefused
+    {
+        warn!("network send failed: {e}");
+    }
+}
+
+/// Drains every datagram waiting on a non-blocking socket, skipping ones that
+/// do not decode.
+pub fn receive_messages(socket: &UdpSocket) -> Vec<(SocketAddr, NetMessage)> {
+    let mut buf = [0u8; MAX_DATAGRAM];
+    let mut messages = Vec::new();
+    loop {
+        match socket.recv_from(&mut buf) {
+            Ok((len, addr)) => {
+                if let Some(message) = decode_message(&buf[..len]) {
+                    messages.push((addr, message));
+                }
+            }
+            Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+            // Reported on some platforms after sending to a closed port
+            Err(e) if e.kind() == ErrorKind::ConnectionRefused => continue,
+            Err(e) => {
+                warn!("network receive failed: {e}");
+                break;
+            }
+        }
+    }
+    messages
+}
@@ -0,0 +1,94 @@
+use std::collections::VecDeque;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+use std::sync::{Arc, Mutex};
+
+use crate::components::NetMessage;
+use crate::helpers::{decode_message, encode_message, receive_messages, send_message};
+
+/// Carries messages between rollback peers. Delivery is best effort: messages
+/// may be late, lost or out of order.
+pub trait PeerTransport: Send + Sync {
+    /// Sends to every peer.
+    fn send(&mut self, message: &NetMessage);
+    /// Everything that arrived since the last call; called once per frame.
+    fn receive(&mut self) -> Vec<NetMessage>;
+}
+
+/// UDP to a fixed set of peers; datagrams from anyone else are ignored.
+pub struct UdpTransport {
+    socket: UdpSocket,
+    peers: Vec<SocketAddr>,
+}
+
+impl UdpTransport {
+    pub fn bind(addr: impl ToSocketAddrs, peers: impl ToSocketAddrs) -> std::io::Result<Self> {
+        let socket = UdpSocket::bind(addr)?;
+        socket.set_nonblocking(true)?;
+        Ok(Self {
+            socket,
+            peers: peers.to_socket_addrs()?.collect(),
+        })
+    }
+}
+
+impl PeerTransport for UdpTransport {
+    fn send(&mut self, message: &NetMessage) {
+        for peer in &self.peers {
+            send_message(&self.socket, Some(*peer), message);
+        }
+    }
+
+    fn receive(&mut self) -> Vec<NetMessage> {
+        receive_messages(&self.socket)
+            .into_iter()
+            .filter(|(addr, _)| self.peers.contains(addr))
+            .map(|(_, message)| message)
+            .collect()
+    }
+}
+
+type Queue = Arc<Mutex<VecDeque<(u64, Vec<u8>)>>>;
+
+/// In-process link between two peers that delivers each message a fixed
+/// number of frames after it was sent, for testing rollback under latency.
+pub struct LoopbackTransport {
+    outbox: Queue,
+    inbox: Queue,
+    latency: u64,
+    frame: u64,
+}
+
+impl LoopbackTransport {
+    /// Both ends of a link with `latency` frames of delay each way.
+    pub fn pair(latency: u64) -> (Self, Self) {
+        let (a, b) = (Queue::default(), Queue::default());
+        let end = |outbox: &Queue, inbox: &Queue| Self {
+            outbox: outbox.clone(),
+            inbox: inbox.clone(),
+            latency,
+            frame: 0,
+        };
+        (end(&a, &b), end(&b, &a))
+    }
+}
+
+impl PeerTransport for LoopbackTransport {
+    fn send(&mut self, message: &NetMessage) {
+        let bytes = encode_message(message);
+        self.outbox.lock().unwrap().push_back((self.frame, bytes));
+    }
+
+    fn receive(&mut self) -> Vec<NetMessage> {
+        self.frame += 1;
+        let mut inbox = self.inbox.lock().unwrap();
+        let mut messages = Vec::new();
+        while let Some((sent, _)) = inbox.front() {
+            if sent + self.latency > self.frame {
+                break;
+            }
+            let (_, bytes) = inbox.pop_front().unwrap();
+            messages.extend(decode_message(&bytes));
+        }
+        messages
+    }
+}
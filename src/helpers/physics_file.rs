@@ -0,0 +1,117 @@
+use std::fmt;
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+
+use crate::components::PhysicsConfig;
+
+#[derive(Debug)]
+pub enum PhysicsConfigError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+    Invalid {
+        field: &'static str,
+        value: f32,
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for PhysicsConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PhysicsConfigError::Io(e) => write!(f, "cannot read physics config: {e}"),
+            PhysicsConfigError::Parse(e) => {
+                write!(f, "malformed physics config at {}: {}", e.position, e.code)
+            }
+            PhysicsConfigError::Invalid {
+                field,
+                value,
+                expected,
+            } => write!(
+                f,
+                "invalid physics config {field}: {value}, expected {expected}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PhysicsConfigError {}
+
+impl From<std::io::Error> for PhysicsConfigError {
+    fn from(e: std::io::Error) -> Self {
+        PhysicsConfigError::Io(e)
+    }
+}
+
+/// Parses a physics config, rejecting values the integrator cannot use and
+/// ones no arena is playable with: nothing may push the wrong way, and
+/// nothing may be so large a tick flings players across the arena.
+pub fn parse_physics_config(text: &str) -> Result<PhysicsConfig, PhysicsConfigError> {
+    let config: PhysicsConfig = ron::from_str(text).map_err(PhysicsConfigError::Parse)?;
+    let PhysicsConfig {
+        drag_rate,
+        move_mult,
+        gravity,
+        jump_velocity,
+    } = config;
+    for (field, value, valid, expected) in [
+        (
+            "drag_rate",
+            drag_rate,
+            drag_rate > 0.0 && drag_rate <= 100.0,
+            "above 0, at most 100",
+        ),
+        (
+            "move_mult",
+            move_mult,
+            (0.0..=1000.0).contains(&move_mult),
+            "0 to 1000",
+        ),
+        (
+            "gravity",
+            gravity,
+            (0.0..=1000.0).contains(&gravity),
+            "0 to 1000",
+        ),
+        (
+            "jump_velocity",
+            jump_velocity,
+            (0.0..=100.0).contains(&jump_velocity),
+            "0 to 100",
+        ),
+    ] {
+        if !valid {
+            return Err(PhysicsConfigError::Invalid {
+                field,
+                value,
+                expected,
+            });
+        }
+    }
+    Ok(config)
+}
+
+#[derive(Default)]
+pub struct PhysicsConfigLoader;
+
+impl AssetLoader for PhysicsConfigLoader {
+    type Asset = PhysicsConfig;
+    type Settings = ();
+    type Error = PhysicsConfigError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<PhysicsConfig, PhysicsConfigError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let text = String::from_utf8(bytes).map_err(std::io::Error::other)?;
+        parse_physics_config(&text)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["physics.ron"]
+    }
+}
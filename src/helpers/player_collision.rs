@@ -0,0 +1,86 @@
+use bevy::prelude::*;
+
+use crate::components::{Arena, Player};
+use crate::constants::GROUND_NORMAL_Y;
+use crate::helpers::push_out_of_obstacles;
+
+/// Player, position, velocity and whether it holds jump.
+type PlayerBody<'a> = (&'a Player, &'a mut Vec3, &'a mut Vec3, bool);
+
+/// Pushes two overlapping players apart, treating each as a vertical capsule
+/// from the feet sphere to the head sphere. One pushed up onto the other's
+/// head lands there.
+pub fn collide_players(a: PlayerBody, b: PlayerBody, jump_velocity: f32) {
+    let (a, a_pos, a_vel, a_jump) = a;
+    let (b, b_pos, b_vel, b_jump) = b;
+
+    // Closest points of the two vertical segments
+    let a_low = a_pos.y + a.radius - a.height;
+    let b_low = b_pos.y + b.radius - b.height;
+    let dy = if b_low > a_pos.y {
+        b_low - a_pos.y
+    } else if a_low > b_pos.y {
+        b_pos.y - a_low
+    } else {
+        0.0
+    };
+    let delta = Vec3::new(b_pos.x - a_pos.x, dy, b_pos.z - a_pos.z);
+    let dist = delta.length();
+    let depth = a.radius + b.radius - dist;
+    if depth <= 0.0 {
+        return;
+    }
+    let normal = if dist > 1e-6 { delta / dist } else { Vec3::X };
+
+    if normal.y > GROUND_NORMAL_Y {
+        // b stands on a's head; a is supported and stays put
+        *b_pos += normal * depth;
+        land(b_vel, b_jump, jump_velocity);
+    } else if normal.y < -GROUND_NORMAL_Y {
+        *a_pos -= normal * depth;
+        land(a_vel, a_jump, jump_velocity);
+    } else {
+        *a_pos -= normal * depth * 0.5;
+        *b_pos += normal * depth * 0.5;
+    }
+}
+
+/// Keeps a player pushed by others inside the arena and out of obstacles.
+pub fn confine_player(arena: &Arena, player: &Player, pos: &mut Vec3) {
+    let scale = arena.half_extent;
+    let bound = scale - player.radius;
+    pos.x = pos.x.clamp(-bound, bound);
+    pos.y = pos.y.clamp(player.height - scale, bound);
+    pos.z = pos.z.clamp(-bound, bound);
+    push_out_of_obstacles(&arena.obstacles, pos, player.radius, player.height);
+}
+
+/// Collides one player with others held where they are, in id order like the
+/// server, for a client predicting its own player against replicated ones.
+pub fn collide_with_players(
+    body: PlayerBody,
+    others: &[(&Player, Vec3)],
+    arena: &Arena,
+    jump_velocity: f32,
+) {
+    let (player, pos, vel, jump) = body;
+    let mut others = others.to_vec();
+    others.sort_by_key(|(p, _)| p.id);
+    for (other, mut other_pos) in others {
+        let mut other_vel = Vec3::ZERO;
+        let us = (player, &mut *pos, &mut *vel, jump);
+        let them = (other, &mut other_pos, &mut other_vel, false);
+        if other.id < player.id {
+            collide_players(them, us, jump_velocity);
+        } else {
+            collide_players(us, them, jump_velocity);
+        }
+    }
+    confine_player(arena, player, pos);
+}
+
+fn land(vel: &mut Vec3, jump: bool, jump_velocity: f32) {
+    if vel.y <= 0.0 {
+        vel.y = if jump { jump_velocity } else { 0.0 };
+    }
+}
@@ -0,0 +1,98 @@
+use bevy::prelude::*;
+
+use crate::components::{Arena, PhysicsConfig, Player, TickInput};
+use crate::constants::GROUND_NORMAL_Y;
+use crate::helpers::push_out_of_obstacles;
+
+/// One tick of a living player's movement: drag, gravity, the input's push
+/// and jump, then the arena walls and obstacles. The simulation and
+/// client-side prediction both move players with this.
+pub fn step_player(
+    translation: &mut Vec3,
+    vel: &mut Vec3,
+    player: &Player,
+    input: &TickInput,
+    dt: f32,
+    arena: &Arena,
+    physics: &PhysicsConfig,
+) {
+    let PhysicsConfig {
+        drag_rate,
+        move_mult,
+        gravity,
+        jump_velocity,
+    } = *physics;
+    let dt = dt.max(1e-6);
+    let drag = (-dt * drag_rate).exp();
+    let diff = 1.0 - drag;
+
+    let dir = input.movement;
+    let jumping = input.jump;
+    let (sin_yaw, cos_yaw) = input.yaw.sin_cos();
+    let acc_x = move_mult * (cos_yaw * dir.x + sin_yaw * dir.y);
+    let acc_z = move_mult * (-sin_yaw * dir.x + cos_yaw * dir.y);
+
+    vel.x -= vel.x * diff;
+    vel.z -= vel.z * diff;
+    vel.y -= gravity * dt;
+
+    vel.x += diff * acc_x / drag_rate;
+    vel.z += diff * acc_z / drag_rate;
+
+    translation.x += (dt - diff / drag_rate) * acc_x / drag_rate + diff * vel.x / drag_rate;
+    translation.y += -0.5 * gravity * dt * dt + vel.y * dt;
+    translation.z += (dt - diff / drag_rate) * acc_z / drag_rate + diff * vel.z / drag_rate;
+
+    let scale = arena.half_extent;
+    let bound = scale - player.radius;
+    let mut pos = *translation;
+    let mut hit_x = false;
+    let mut hit_y = false;
+    let mut hit_z = false;
+    if pos.x < -bound {
+        pos.x = -bound;
+        hit_x = true;
+    }
+    if pos.x > bound {
+        pos.x = bound;
+        hit_x = true;
+    }
+    if pos.y < player.height - scale {
+        pos.y = player.height - scale;
+        hit_y = true;
+    }
+    if pos.y > bound {
+        pos.y = bound;
+        hit_y = true;
+    }
+    if pos.z < -bound {
+        pos.z = -bound;
+        hit_z = true;
+    }
+    if pos.z > bound {
+        pos.z = bound;
+        hit_z = true;
+    }
+    if hit_x {
+        vel.x = 0.0;
+    }
+    if hit_z {
+        vel.z = 0.0;
+    }
+    if hit_y {
+        vel.y = if jumping { jump_velocity } else { 0.0 };
+    }
+    let normals = push_out_of_obstacles(&arena.obstacles, &mut pos, player.radius, player.height);
+    for normal in normals {
+        if normal.y > GROUND_NORMAL_Y {
+            vel.y = if jumping { jump_velocity } else { 0.0 };
+        } else {
+            // Slide along walls and ceilings
+            let into = vel.dot(normal);
+            if into < 0.0 {
+                *vel -= normal * into;
+            }
+        }
+    }
+    *translation = pos;
+}
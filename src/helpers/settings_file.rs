@@ -0,0 +1,36 @@
+use std::path::Path;
+
+use bevy::prelude::*;
+
+use super::{clamp_sliders, load_config};
+use crate::components::{PlayerSettings, SettingsRes};
+use crate::constants::{MAX_DEAD_ZONE, MAX_PLAYER_COUNT, SETTINGS_VERSION};
+
+/// Loads settings, discarding files written by a newer version, padding the
+/// per-player list so every player slot has an entry and clamping sliders and
+/// dead zones into their usable ranges.
+pub fn load_settings(path: impl AsRef<Path>) -> SettingsRes {
+    let path = path.as_ref();
+    let mut settings: SettingsRes = load_config(path);
+    if settings.version > SETTINGS_VERSION {
+        warn!(
+            "settings file {} has unsupported version {}, using defaults",
+            path.display(),
+            settings.version
+        );
+        return SettingsRes::default();
+    }
+    settings.version = SETTINGS_VERSION;
+    settings
+        .players
+        .resize_with(MAX_PLAYER_COUNT, PlayerSettings::default);
+    for player in &mut settings.players {
+        clamp_sliders(player);
+        player.gamepad_dead_zone = if player.gamepad_dead_zone.is_nan() {
+            PlayerSettings::default().gamepad_dead_zone
+        } else {
+            player.gamepad_dead_zone.clamp(0.0, MAX_DEAD_ZONE)
+        };
+    }
+    settings
+}
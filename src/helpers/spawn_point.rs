@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+
+use crate::components::Obstacle;
+use crate::helpers::line_of_sight;
+
+/// Half-angle of the view cone an enemy is assumed to see.
+const ENEMY_VIEW_COS: f32 = 0.5; // 60 degrees
+
+/// Picks the candidate seen by the fewest enemies and, among those, the one
+/// farthest from the nearest enemy. `enemies` are (eye position, view
+/// direction); obstacles block their sight. Ties keep the earliest candidate
+/// so the result is stable.
+pub fn choose_spawn_point(
+    candidates: &[Vec3],
+    enemies: &[(Vec3, Vec3)],
+    obstacles: &[Obstacle],
+) -> Option<Vec3> {
+    let mut best: Option<(usize, f32, Vec3)> = None;
+    for &c in candidates {
+        let seen_by = enemies
+            .iter()
+            .filter(|(eye, dir)| {
+                dir.dot((c - *eye).normalize_or_zero()) > ENEMY_VIEW_COS
+                    && line_of_sight(obstacles, *eye, c)
+            })
+            .count();
+        let nearest = enemies
+            .iter()
+            .map(|(eye, _)| eye.distance(c))
+            .fold(f32::INFINITY, f32::min);
+        let better = match best {
+            None => true,
+            Some((b_seen, b_nearest, _)) => {
+                seen_by < b_seen || (seen_by == b_seen && nearest > b_nearest)
+            }
+        };
+        if better {
+            best = Some((seen_by, nearest, c));
+        }
+    }
+    best.map(|(_, _, c)| c)
+}
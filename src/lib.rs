@@ -1,13 +1,21 @@
 pub mod constants;
 pub mod components;
+pub mod events;
+pub mod headless;
 pub mod helpers;
+pub mod plugins;
 pub mod systems;
 
 use bevy::prelude::*;
 
 pub fn run() {
-    App::new()
-        .add_plugins(DefaultPlugins.set(WindowPlugin {
+    if helpers::cli_flag("--headless") {
+        headless::run_headless();
+        return;
+    }
+    let mut app = App::new();
+    app.add_plugins((
+        DefaultPlugins.set(WindowPlugin {
             primary_window: Some(Window {
                 title: "Example splitscreen shooter game (Bevy)".into(),
                 resolution: (constants::WINDOW_WIDTH, constants::WINDOW_HEIGHT).into(),
@@ -15,32 +23,151 @@ pub fn run() {
                 ..default()
             }),
             ..default()
-        }))
-        .insert_resource(ClearColor(Color::BLACK))
-        .insert_resource(components::PlayerCount(1))
-        .insert_resource(components::MouseDelta::default())
-        .insert_resource(helpers::init_edges(constants::MAP_BOX_SCALE))
-        .insert_resource(components::GameMode(components::AppMode::Playing))
-        .insert_resource(components::SettingsRes {
-            sensitivity: 0.0025,
-            crosshair_half: constants::CROSS_WORLD_HALF,
-        })
-        .add_systems(Startup, (systems::setup_players, systems::setup_cameras))
-        .add_systems(
-            Update,
-            (
-                systems::esc_menu_toggle,
-                systems::accumulate_mouse_motion,
-                systems::handle_input,
-                systems::handle_settings_input,
-                systems::update_physics,
-                systems::handle_shooting,
-                systems::draw_world_gizmos,
-                systems::draw_crosshair_gizmos,
-                systems::update_camera_transforms,
-                systems::update_camera_viewports,
-                systems::update_cursor_visibility,
-            ),
-        )
-        .run();
+        }),
+        plugins::WoodeneyePlugin,
+    ))
+    .insert_resource(components::MapPath(
+        helpers::cli_value("--map").unwrap_or_else(|| constants::DEFAULT_MAP_PATH.into()),
+    ));
+    apply_match_flags(&mut app);
+    let replay = replay_demo();
+    if replay.is_some() || helpers::cli_flag("--spectate") {
+        app.insert_resource(components::SpectatorEnabled);
+    }
+    if let Some(demo) = replay {
+        app.add_plugins(plugins::ReplayPlugin(demo));
+    }
+    app.run();
+}
+
+/// Overrides the plugin defaults with the match flags shared by the windowed
+/// and headless modes.
+fn apply_match_flags(app: &mut App) {
+    app.insert_resource(match_rules())
+        .insert_resource(match_rng())
+        .insert_resource(Time::<Fixed>::from_hz(tick_rate()))
+        .insert_resource(components::ResultsPath(
+            helpers::cli_value("--results").map(Into::into),
+        ));
+    if let Some(path) = helpers::cli_value("--record") {
+        app.insert_resource(components::DemoRecorder::new(path));
+    }
+    apply_network_flags(app);
+}
+
+/// `--host PORT` serves the match to remote players over UDP; `--connect
+/// HOST:PORT` joins one; `--peer HOST:PORT [--port PORT] [--slot 1|2]` plays
+/// a two-player rollback match against another peer. Exits when the socket
+/// cannot be opened.
+fn apply_network_flags(app: &mut App) {
+    if let Some(port) = helpers::cli_value("--host") {
+        let Ok(port) = port.parse::<u16>() else {
+            eprintln!("--host: {port} is not a port number");
+            std::process::exit(1);
+        };
+        match components::NetServer::bind(("0.0.0.0", port)) {
+            Ok(server) => {
+                info!("hosting on UDP port {port}");
+                app.insert_resource(server);
+            }
+            Err(e) => {
+                eprintln!("--host {port}: {e}");
+                std::process::exit(1);
+            }
+        }
+    }
+    if let Some(addr) = helpers::cli_value("--connect") {
+        match components::NetClient::connect(addr.as_str()) {
+            Ok(client) => {
+                info!("connecting to {addr}");
+                app.insert_resource(client);
+            }
+            Err(e) => {
+                eprintln!("--connect {addr}: {e}");
+                std::process::exit(1);
+            }
+        }
+    }
+    if let Some(addr) = helpers::cli_value("--peer") {
+        app.insert_resource(rollback_session(&addr));
+    }
+}
+
+/// Both peers pass the same `--seed`, map and physics; one of them `--slot
+/// 2`. The local port defaults to the peer's.
+fn rollback_session(addr: &str) -> components::RollbackSession {
+    let port = helpers::cli_value("--port")
+        .or_else(|| addr.rsplit_once(':').map(|(_, port)| port.to_string()))
+        .and_then(|v| v.parse::<u16>().ok())
+        .unwrap_or_else(|| {
+            eprintln!("--peer {addr}: pass the local UDP port with --port");
+            std::process::exit(1);
+        });
+    let slot = match helpers::cli_value("--slot").as_deref() {
+        None | Some("1") => 0,
+        Some("2") => 1,
+        Some(other) => {
+            eprintln!("--slot: {other} is not 1 or 2");
+            std::process::exit(1);
+        }
+    };
+    match helpers::UdpTransport::bind(("0.0.0.0", port), addr) {
+        Ok(transport) => {
+            info!("playing {addr} from UDP port {port} as player {}", slot + 1);
+            components::RollbackSession::new(transport, slot, vec![0, 1])
+        }
+        Err(e) => {
+            eprintln!("--peer {addr}: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// The demo named by `--replay PATH`; exits when it cannot be loaded.
+fn replay_demo() -> Option<components::Demo> {
+    let path = helpers::cli_value("--replay")?;
+    match helpers::load_demo(&path) {
+        Ok(demo) => Some(demo),
+        Err(e) => {
+            eprintln!("{path}: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Simulation ticks per second from `--tick-rate`.
+fn tick_rate() -> f64 {
+    helpers::cli_value("--tick-rate")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(constants::TICK_RATE)
+}
+
+/// Seeds the match RNG from `--seed N`, or randomly; the seed is logged so a
+/// match can be reproduced.
+fn match_rng() -> components::MatchRng {
+    let seed = helpers::cli_value("--seed")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or_else(rand::random);
+    info!("match seed: {seed}");
+    components::MatchRng::seeded(seed)
+}
+
+/// Match rules from the command line: `--instagib`, `--score-limit N`,
+/// `--time-limit SECONDS` (0 disables a limit) and `--max-rewind SECONDS`.
+fn match_rules() -> components::MatchRules {
+    let mut rules = if helpers::cli_flag("--instagib") {
+        components::MatchRules::instagib()
+    } else {
+        components::MatchRules::default()
+    };
+    if let Some(n) = helpers::cli_value("--score-limit").and_then(|v| v.parse().ok()) {
+        rules.score_limit = (n > 0).then_some(n);
+    }
+    if let Some(secs) = helpers::cli_value("--time-limit").and_then(|v| v.parse::<f32>().ok()) {
+        rules.time_limit = (secs > 0.0).then_some(secs);
+    }
+    if let Some(secs) = helpers::cli_value("--max-rewind").and_then(|v| v.parse::<f32>().ok()) {
+        rules.max_rewind = components::MatchRules::clamp_rewind(secs);
+    }
+    rules
 }
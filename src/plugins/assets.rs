@@ -0,0 +1,71 @@
+use bevy::asset::LoadState;
+use bevy::prelude::*;
+
+use super::GameSet;
+use crate::components::{Demo, MapDef, MapHandle, MapPath, PhysicsConfig, PhysicsConfigHandle};
+use crate::helpers::{MapLoader, PhysicsConfigLoader};
+use crate::systems;
+
+/// Loads the map and physics config through the asset server and applies
+/// them whenever they (re)load; ticks wait until they have. Replays use the
+/// recorded ones instead. Needs `AssetPlugin`.
+pub struct GameAssetsPlugin;
+
+impl Plugin for GameAssetsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MapPath>()
+            .init_asset::<MapDef>()
+            .init_asset_loader::<MapLoader>()
+            .init_asset::<PhysicsConfig>()
+            .init_asset_loader::<PhysicsConfigLoader>()
+            .configure_sets(
+                FixedUpdate,
+                (
+                    GameSet::Input,
+                    GameSet::Physics,
+                    GameSet::Combat,
+                    GameSet::Match,
+                )
+                    .run_if(resource_exists::<Demo>.or(assets_applied)),
+            )
+            .add_systems(Startup, (systems::load_map, systems::load_physics_config))
+            .add_systems(
+                Update,
+                (systems::apply_map, systems::apply_physics_config)
+                    .before(GameSet::Input)
+                    .run_if(not(resource_exists::<Demo>)),
+            );
+    }
+}
+
+/// Whether the map and physics in play are the loaded files, so no tick runs
+/// on the built-in ones while they load, nor on a stale one after an edit. A
+/// file that fails to load leaves the built-in one in play.
+fn assets_applied(
+    server: Res<AssetServer>,
+    maps: Res<Assets<MapDef>>,
+    map_handle: Option<Res<MapHandle>>,
+    map: Res<MapDef>,
+    configs: Res<Assets<PhysicsConfig>>,
+    physics_handle: Option<Res<PhysicsConfigHandle>>,
+    physics: Res<PhysicsConfig>,
+) -> bool {
+    fn applied<A: Asset + PartialEq>(
+        server: &AssetServer,
+        assets: &Assets<A>,
+        handle: Option<&Handle<A>>,
+        current: &A,
+    ) -> bool {
+        handle.is_none_or(|handle| {
+            matches!(server.load_state(handle), LoadState::Failed(_))
+                || assets.get(handle) == Some(current)
+        })
+    }
+    applied(&server, &maps, map_handle.as_deref().map(|h| &h.0), &map)
+        && applied(
+            &server,
+            &configs,
+            physics_handle.as_deref().map(|h| &h.0),
+            &physics,
+        )
+}
@@ -0,0 +1,30 @@
+use bevy::prelude::*;
+
+use super::GameSet;
+use crate::components::{AppMode, Demo, NetClient, RollbackSession, SettingsRes};
+use crate::systems;
+
+/// AI players for the slots nobody has joined, at the difficulty picked per
+/// slot in the settings; without settings there are none. Scripted players
+/// leave bots their slots, while replays replay what bots did.
+pub struct BotPlugin;
+
+impl Plugin for BotPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            systems::assign_bots
+                .after(systems::assign_input_devices)
+                // A client's opponents are the server's, a peer's are the other peers
+                .run_if(
+                    in_state(AppMode::Playing)
+                        .and(resource_exists::<SettingsRes>)
+                        .and(not(resource_exists::<Demo>))
+                        .and(not(resource_exists::<NetClient>))
+                        .and(not(resource_exists::<RollbackSession>)),
+                )
+                .in_set(GameSet::Input),
+        )
+        .add_systems(FixedUpdate, systems::drive_bots.in_set(GameSet::Input));
+    }
+}
@@ -0,0 +1,32 @@
+use bevy::prelude::*;
+
+use super::GameSet;
+use crate::components::SpectatorEnabled;
+use crate::systems;
+
+/// One camera per player in split screen, an optional spectator camera and
+/// the UI overlay camera.
+pub struct PlayerCameraPlugin;
+
+impl Plugin for PlayerCameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Startup,
+            (
+                systems::setup_cameras,
+                systems::setup_spectator
+                    .after(systems::setup_players)
+                    .run_if(resource_exists::<SpectatorEnabled>),
+            ),
+        )
+        .add_systems(
+            Update,
+            (
+                systems::update_camera_transforms,
+                systems::update_spectators.after(systems::update_camera_transforms),
+                systems::update_camera_viewports,
+            )
+                .in_set(GameSet::Camera),
+        );
+    }
+}
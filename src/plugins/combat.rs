@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+
+use super::GameSet;
+use crate::events::{PlayerHit, PlayerKilled, PlayerRespawned, ShotFired};
+use crate::systems;
+
+/// Hitscan shooting with lag compensation, damage, death and respawn, plus
+/// their events.
+pub struct CombatPlugin;
+
+impl Plugin for CombatPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ShotFired>()
+            .add_event::<PlayerHit>()
+            .add_event::<PlayerKilled>()
+            .add_event::<PlayerRespawned>()
+            .add_systems(
+                FixedUpdate,
+                // Scores are kept within the tick so a rollback rewinds them too
+                (
+                    systems::handle_shooting,
+                    systems::respawn_players,
+                    systems::record_kills,
+                )
+                    .chain()
+                    .in_set(GameSet::Combat),
+            )
+            .add_systems(
+                FixedUpdate,
+                systems::record_position_history
+                    .after(systems::advance_sim_tick)
+                    .in_set(GameSet::Match),
+            )
+            .add_systems(Update, systems::log_gameplay_events.in_set(GameSet::Combat));
+    }
+}
@@ -0,0 +1,72 @@
+use bevy::prelude::*;
+
+use super::GameSet;
+use crate::components::{AppMode, Demo, DemoRecorder, InputScript, MapPath, MatchRng, PlayerCount};
+use crate::systems;
+
+/// Records every tick's inputs while a `DemoRecorder` resource is present.
+pub struct DemoPlugin;
+
+impl Plugin for DemoPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            systems::record_tick_inputs
+                .after(systems::apply_input_script)
+                .after(systems::drive_bots)
+                .after(systems::apply_remote_inputs)
+                .in_set(GameSet::Input)
+                .run_if(in_state(AppMode::Playing).and(resource_exists::<DemoRecorder>)),
+        )
+        .add_systems(
+            OnEnter(AppMode::MatchOver),
+            systems::write_demo.run_if(resource_exists::<DemoRecorder>),
+        )
+        .add_systems(
+            Last,
+            systems::write_demo.run_if(on_event::<AppExit>.and(resource_exists::<DemoRecorder>)),
+        );
+    }
+}
+
+/// Plays a recorded match in place of live input: same seed, rules, tick rate
+/// and player slots, the recorded map and physics rather than the files on
+/// disk, and every player driven by the recording, view ticks included. Add
+/// it after the match flags so the recorded setup wins.
+pub struct ReplayPlugin(pub Demo);
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        let demo = self.0.clone();
+        info!(
+            "replaying {} ticks of {}, recorded with seed {}",
+            demo.ticks.len(),
+            demo.map,
+            demo.seed
+        );
+        app.insert_resource(MatchRng::seeded(demo.seed))
+            .insert_resource(demo.rules.clone())
+            .insert_resource(Time::<Fixed>::from_hz(demo.tick_rate))
+            .insert_resource(MapPath(demo.map.clone()))
+            .insert_resource(PlayerCount(demo.players))
+            .insert_resource(InputScript(Box::new({
+                let demo = demo.clone();
+                move |tick, player| demo.input(tick, player)
+            })))
+            .insert_resource(demo)
+            .add_systems(
+                FixedUpdate,
+                (
+                    systems::apply_demo_setup,
+                    systems::apply_demo_view_ticks.after(systems::apply_input_script),
+                )
+                    .in_set(GameSet::Input),
+            )
+            .add_systems(
+                Update,
+                systems::end_replay
+                    .run_if(in_state(AppMode::Playing))
+                    .in_set(GameSet::Match),
+            );
+    }
+}
@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+
+use super::GameSet;
+use crate::components::{ActionBindings, ActionState, AppMode, InputScript, MouseDelta};
+use crate::constants::{BINDINGS_PATH, SETTINGS_PATH};
+use crate::helpers::{load_config, load_settings};
+use crate::systems;
+
+/// Keyboards, mice and gamepads: device assignment, bindings, per-player
+/// settings and the cursor grab.
+pub struct PlayerInputPlugin;
+
+impl Plugin for PlayerInputPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(MouseDelta::default())
+            .insert_resource(load_settings(SETTINGS_PATH))
+            .insert_resource(load_config::<ActionBindings>(BINDINGS_PATH))
+            .init_resource::<ActionState>()
+            .add_systems(
+                PreUpdate,
+                (
+                    systems::accumulate_mouse_motion.run_if(in_state(AppMode::Playing)),
+                    systems::update_action_state,
+                    // Replays drive the players from the recording instead
+                    (systems::handle_input, systems::sample_tick_input).run_if(
+                        in_state(AppMode::Playing).and(not(resource_exists::<InputScript>)),
+                    ),
+                )
+                    .chain()
+                    .in_set(GameSet::Input),
+            )
+            .add_systems(
+                Update,
+                (
+                    systems::esc_menu_toggle,
+                    systems::assign_input_devices.run_if(in_state(AppMode::Playing)),
+                )
+                    .in_set(GameSet::Input),
+            )
+            .add_systems(OnEnter(AppMode::Playing), systems::update_cursor_visibility)
+            .add_systems(OnExit(AppMode::Playing), systems::update_cursor_visibility);
+    }
+}
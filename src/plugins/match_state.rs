@@ -0,0 +1,73 @@
+use bevy::prelude::*;
+
+use super::GameSet;
+use crate::components::{
+    AppMode, InputScript, MatchRng, MatchRules, NetClient, PlayerCount, ResultsPath, Scoreboard,
+    SimTick,
+};
+use crate::constants::TICK_RATE;
+use crate::systems;
+
+/// App modes, match rules and scoring, the fixed timestep and the ordering of
+/// every `GameSet`.
+pub struct MatchPlugin;
+
+impl Plugin for MatchPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_state::<AppMode>()
+            .insert_resource(PlayerCount(1))
+            .init_resource::<MatchRules>()
+            .init_resource::<MatchRng>()
+            .init_resource::<Scoreboard>()
+            .init_resource::<SimTick>()
+            .init_resource::<ResultsPath>()
+            .insert_resource(Time::<Fixed>::from_hz(TICK_RATE))
+            .configure_sets(PreUpdate, GameSet::Input.after(bevy::input::InputSystem))
+            .configure_sets(
+                FixedUpdate,
+                (
+                    GameSet::Input,
+                    GameSet::Physics,
+                    GameSet::Combat,
+                    GameSet::Match,
+                )
+                    .chain()
+                    .run_if(in_state(AppMode::Playing).and(not(match_ending))),
+            )
+            .configure_sets(
+                Update,
+                (
+                    GameSet::Input,
+                    GameSet::Combat,
+                    GameSet::Match,
+                    GameSet::Camera,
+                    GameSet::Render,
+                    GameSet::Ui,
+                )
+                    .chain(),
+            )
+            .add_systems(Startup, systems::setup_players)
+            .add_systems(
+                FixedUpdate,
+                (
+                    systems::apply_input_script
+                        .run_if(resource_exists::<InputScript>)
+                        .in_set(GameSet::Input),
+                    systems::advance_sim_tick.in_set(GameSet::Match),
+                    // A client's match ends with the server's
+                    systems::check_match_end
+                        .after(systems::advance_sim_tick)
+                        .run_if(not(resource_exists::<NetClient>))
+                        .in_set(GameSet::Match),
+                ),
+            )
+            .add_systems(OnEnter(AppMode::MatchOver), systems::write_match_results)
+            .add_systems(OnExit(AppMode::MatchOver), systems::reset_match);
+    }
+}
+
+/// Whether a tick this frame ended the match; the rest of the frame's ticks
+/// wait for the state change so every run ends on the same tick.
+fn match_ending(next: Res<NextState<AppMode>>) -> bool {
+    matches!(*next, NextState::Pending(AppMode::MatchOver))
+}
@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+
+use super::GameSet;
+use crate::components::{AppMode, MenuFocus, RebindState, SettingsSelection};
+use crate::systems;
+
+/// Pause menu, settings, key rebinding and the scoreboard overlay.
+pub struct MenuPlugin;
+
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SettingsSelection>()
+            .init_resource::<RebindState>()
+            .init_resource::<MenuFocus>()
+            .add_systems(
+                Startup,
+                (
+                    systems::setup_scoreboard.after(systems::setup_cameras),
+                    systems::setup_menu,
+                    systems::setup_rebind_screen,
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    systems::save_settings,
+                    systems::update_scoreboard,
+                    (systems::handle_menu_input, systems::update_menu)
+                        .run_if(in_state(AppMode::Menu).or(in_state(AppMode::Settings))),
+                    systems::handle_settings_input.run_if(in_state(AppMode::Settings)),
+                    (systems::rebind_actions, systems::update_rebind_screen)
+                        .run_if(in_state(AppMode::Bindings)),
+                )
+                    .in_set(GameSet::Ui),
+            );
+        for mode in [
+            AppMode::Playing,
+            AppMode::Menu,
+            AppMode::Settings,
+            AppMode::Bindings,
+            AppMode::MatchOver,
+        ] {
+            app.add_systems(OnEnter(mode), systems::show_menu_page);
+        }
+    }
+}
@@ -0,0 +1,86 @@
+//! The game as Bevy plugins. `WoodeneyePlugin` is everything `run()` uses on
+//! top of `DefaultPlugins`; `SimulationPlugin` is the windowless core.
+
+mod assets;
+mod bots;
+mod camera;
+mod combat;
+mod demo;
+mod input;
+mod match_state;
+mod menu;
+mod network;
+mod physics;
+mod rendering;
+mod rollback;
+
+use bevy::prelude::*;
+
+pub use assets::GameAssetsPlugin;
+pub use bots::BotPlugin;
+pub use camera::PlayerCameraPlugin;
+pub use combat::CombatPlugin;
+pub use demo::{DemoPlugin, ReplayPlugin};
+pub use input::PlayerInputPlugin;
+pub use match_state::MatchPlugin;
+pub use menu::MenuPlugin;
+pub use network::NetworkPlugin;
+pub use physics::PhysicsPlugin;
+pub use rendering::WorldRenderPlugin;
+pub use rollback::RollbackPlugin;
+
+/// Stages of a frame and of a fixed tick. In `FixedUpdate` they run
+/// `Input -> Physics -> Combat -> Match` while playing; in `Update`
+/// `Input -> Combat -> Match -> Camera -> Render -> Ui`; in `PreUpdate` only
+/// `Input` runs, after Bevy has read the devices.
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GameSet {
+    /// Devices to actions to each player's `TickInput`.
+    Input,
+    /// Movement and collision.
+    Physics,
+    /// Shots, damage, respawns and their events.
+    Combat,
+    /// Tick counter, score keeping and the end of the match.
+    Match,
+    /// Cameras follow each player's eye, before anything is drawn.
+    Camera,
+    /// Gizmos drawing the world.
+    Render,
+    /// Menus and overlays.
+    Ui,
+}
+
+/// Match state, physics, combat, bots, demo recording and both kinds of
+/// networking: enough to run a match without a window.
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            MatchPlugin,
+            PhysicsPlugin,
+            CombatPlugin,
+            BotPlugin,
+            DemoPlugin,
+            NetworkPlugin,
+            RollbackPlugin,
+        ));
+    }
+}
+
+/// The whole game; add it after `DefaultPlugins`.
+pub struct WoodeneyePlugin;
+
+impl Plugin for WoodeneyePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            SimulationPlugin,
+            GameAssetsPlugin,
+            PlayerInputPlugin,
+            PlayerCameraPlugin,
+            WorldRenderPlugin,
+            MenuPlugin,
+        ));
+    }
+}
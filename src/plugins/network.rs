@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+
+use super::GameSet;
+use crate::components::{NetClient, NetServer};
+use crate::events::ServerMessage;
+use crate::systems;
+
+/// LAN play over UDP. With a `NetServer` the match also runs the slots of
+/// remote players; with a `NetClient` the server's simulation replaces ours:
+/// we send our input, predict our own movement and show its snapshots.
+pub struct NetworkPlugin;
+
+impl Plugin for NetworkPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ServerMessage>()
+            .configure_sets(
+                FixedUpdate,
+                (GameSet::Physics, GameSet::Combat).run_if(not(resource_exists::<NetClient>)),
+            )
+            .add_systems(
+                PreUpdate,
+                (
+                    systems::receive_client_messages.run_if(resource_exists::<NetServer>),
+                    (
+                        systems::receive_server_messages,
+                        (systems::join_server, systems::apply_snapshots),
+                        systems::reconcile_prediction,
+                    )
+                        .chain()
+                        .run_if(resource_exists::<NetClient>),
+                )
+                    .in_set(GameSet::Input),
+            )
+            .add_systems(
+                FixedUpdate,
+                (
+                    systems::apply_remote_inputs
+                        .in_set(GameSet::Input)
+                        .run_if(resource_exists::<NetServer>),
+                    // Also while the match is over, so the server still sees us
+                    (systems::send_client_input, systems::predict_local_player)
+                        .chain()
+                        .after(GameSet::Input)
+                        .before(GameSet::Physics)
+                        .run_if(resource_exists::<NetClient>),
+                    systems::send_snapshots
+                        .after(GameSet::Match)
+                        .run_if(resource_exists::<NetServer>),
+                ),
+            )
+            .add_systems(
+                Last,
+                systems::leave_server.run_if(on_event::<AppExit>.and(resource_exists::<NetClient>)),
+            );
+    }
+}
@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+
+use super::GameSet;
+use crate::components::{Arena, MapDef, PhysicsConfig, SpawnPoints};
+use crate::systems;
+
+/// Movement, gravity and collision against the arena and other players.
+pub struct PhysicsPlugin;
+
+impl Plugin for PhysicsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MapDef>()
+            .init_resource::<Arena>()
+            .init_resource::<SpawnPoints>()
+            .init_resource::<PhysicsConfig>()
+            .add_systems(
+                FixedUpdate,
+                (
+                    systems::save_previous_translations,
+                    systems::update_physics,
+                    systems::resolve_player_collisions,
+                )
+                    .chain()
+                    .in_set(GameSet::Physics),
+            );
+    }
+}
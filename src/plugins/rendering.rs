@@ -0,0 +1,24 @@
+use bevy::prelude::*;
+
+use super::GameSet;
+use crate::components::{AppMode, MapColors};
+use crate::constants::MAP_BOX_SCALE;
+use crate::helpers::init_edges;
+use crate::systems;
+
+/// Draws the arena, players and crosshairs with gizmos.
+pub struct WorldRenderPlugin;
+
+impl Plugin for WorldRenderPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ClearColor(Color::BLACK))
+            .insert_resource(init_edges(MAP_BOX_SCALE as f32, MAP_BOX_SCALE as usize))
+            .init_resource::<MapColors>()
+            .add_systems(
+                Update,
+                (systems::draw_world_gizmos, systems::draw_crosshair_gizmos)
+                    .run_if(in_state(AppMode::Playing))
+                    .in_set(GameSet::Render),
+            );
+    }
+}
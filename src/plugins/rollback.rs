@@ -0,0 +1,69 @@
+use bevy::app::RunFixedMainLoopSystem;
+use bevy::prelude::*;
+
+use super::GameSet;
+use crate::components::{AppMode, RollbackSession, SimTick};
+use crate::systems;
+
+/// Peer-to-peer play with rollback while a `RollbackSession` is present:
+/// every peer runs the whole match, saving it before each tick, and replays
+/// the ticks a late input proves were guessed wrong. The match only ends
+/// once every peer's inputs confirm it, and goes on without a peer that goes
+/// quiet.
+pub struct RollbackPlugin;
+
+impl Plugin for RollbackPlugin {
+    fn build(&self, app: &mut App) {
+        app.configure_sets(
+            FixedUpdate,
+            (
+                GameSet::Input,
+                GameSet::Physics,
+                GameSet::Combat,
+                GameSet::Match,
+            )
+                .run_if(can_advance),
+        )
+        .add_systems(
+            Startup,
+            systems::take_rollback_slot
+                .after(systems::setup_players)
+                .after(systems::setup_cameras)
+                .run_if(resource_exists::<RollbackSession>),
+        )
+        .add_systems(
+            RunFixedMainLoop,
+            (
+                systems::rollback_late_inputs,
+                systems::drop_silent_peers,
+                systems::end_rollback_match,
+            )
+                .chain()
+                .in_set(RunFixedMainLoopSystem::BeforeFixedMainLoop)
+                .run_if(in_state(AppMode::Playing).and(resource_exists::<RollbackSession>)),
+        )
+        .add_systems(
+            FixedUpdate,
+            (
+                systems::save_rollback_state.before(systems::apply_input_script),
+                systems::apply_peer_inputs
+                    .after(systems::apply_input_script)
+                    .after(systems::drive_bots)
+                    .before(systems::record_tick_inputs),
+            )
+                .in_set(GameSet::Input)
+                .run_if(resource_exists::<RollbackSession>),
+        )
+        .add_systems(
+            Update,
+            systems::send_peer_inputs
+                .in_set(GameSet::Input)
+                .run_if(resource_exists::<RollbackSession>),
+        );
+    }
+}
+
+/// Holds the ticks back while a peer's inputs lag too far behind to guess.
+fn can_advance(tick: Res<SimTick>, session: Option<Res<RollbackSession>>) -> bool {
+    session.is_none_or(|session| session.can_advance(tick.0))
+}
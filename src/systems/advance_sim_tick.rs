@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+use crate::components::SimTick;
+
+pub fn advance_sim_tick(mut tick: ResMut<SimTick>) {
+    tick.0 += 1;
+}
@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+
+use crate::components::{Demo, PhysicsConfig, SimTick};
+use crate::helpers::insert_map;
+
+/// Puts the recorded map and physics in play on the ticks they were in force
+/// from, whatever the files on disk say now.
+pub fn apply_demo_setup(
+    mut commands: Commands,
+    demo: Res<Demo>,
+    tick: Res<SimTick>,
+    mut physics: ResMut<PhysicsConfig>,
+) {
+    if let Some(map) = demo.map_change(tick.0) {
+        insert_map(&mut commands, map);
+    }
+    if let Some(recorded) = demo.physics_change(tick.0) {
+        *physics = recorded;
+    }
+}
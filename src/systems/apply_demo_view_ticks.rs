@@ -0,0 +1,19 @@
+use bevy::prelude::*;
+
+use crate::components::{Demo, Player, SimTick, ViewTick};
+
+/// Gives replayed players the view tick they shot with when recorded, so
+/// lag-compensated hits land as they did in the match.
+pub fn apply_demo_view_ticks(
+    mut commands: Commands,
+    demo: Res<Demo>,
+    tick: Res<SimTick>,
+    q: Query<(Entity, &Player)>,
+) {
+    for (entity, player) in &q {
+        match demo.view_tick(tick.0, player.id) {
+            Some(view) => commands.entity(entity).insert(ViewTick(view)),
+            None => commands.entity(entity).remove::<ViewTick>(),
+        };
+    }
+}
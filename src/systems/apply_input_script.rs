@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+
+use crate::components::{Bot, InputScript, Player, RemotePlayer, Replicated, SimTick, TickInput};
+
+/// Bots and networked players are driven elsewhere.
+type ScriptedFilter = (Without<Bot>, Without<RemotePlayer>, Without<Replicated>);
+
+/// Feeds every local player but bots the scripted input for the upcoming tick, and
+/// turns them to match so their cameras follow the script.
+pub fn apply_input_script(
+    script: Res<InputScript>,
+    tick: Res<SimTick>,
+    mut q: Query<(&mut Player, &mut TickInput), ScriptedFilter>,
+) {
+    for (mut player, mut input) in &mut q {
+        *input = (script.0)(tick.0, player.id);
+        player.yaw = input.yaw;
+        player.pitch = input.pitch;
+    }
+}
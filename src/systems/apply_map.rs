@@ -0,0 +1,30 @@
+use bevy::prelude::*;
+
+use crate::components::{MapDef, MapHandle};
+use crate::helpers::insert_map;
+
+/// Puts the map asset in play whenever the map
+/// asset loads or is edited on disk. Load errors are logged by the asset
+/// server and keep the current map in place.
+pub fn apply_map(
+    mut commands: Commands,
+    mut events: EventReader<AssetEvent<MapDef>>,
+    maps: Res<Assets<MapDef>>,
+    handle: Option<Res<MapHandle>>,
+) {
+    let Some(handle) = handle else {
+        return;
+    };
+    let changed = events
+        .read()
+        .any(|e| e.is_loaded_with_dependencies(&handle.0) || e.is_modified(&handle.0));
+    let Some(map) = maps.get(&handle.0).filter(|_| changed) else {
+        return;
+    };
+    info!(
+        "loaded map with {} obstacles and {} spawn points",
+        map.obstacles.len(),
+        map.spawn_points.len()
+    );
+    insert_map(&mut commands, map);
+}
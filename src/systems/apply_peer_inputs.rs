@@ -0,0 +1,17 @@
+use bevy::prelude::*;
+
+use crate::components::{Player, RollbackSession, SimTick, TickInput};
+
+/// Gives every player this tick's input from the session: ours as just
+/// sampled or as first played, the others' as received or guessed.
+pub fn apply_peer_inputs(
+    tick: Res<SimTick>,
+    mut session: ResMut<RollbackSession>,
+    mut q: Query<(&mut Player, &mut TickInput)>,
+) {
+    for (mut player, mut input) in &mut q {
+        *input = session.input_for(tick.0, player.id, *input);
+        player.yaw = input.yaw;
+        player.pitch = input.pitch;
+    }
+}
@@ -0,0 +1,22 @@
+use bevy::prelude::*;
+
+use crate::components::{PhysicsConfig, PhysicsConfigHandle};
+
+/// Copies the physics config asset into the resource whenever it (re)loads.
+pub fn apply_physics_config(
+    mut events: EventReader<AssetEvent<PhysicsConfig>>,
+    configs: Res<Assets<PhysicsConfig>>,
+    handle: Option<Res<PhysicsConfigHandle>>,
+    mut physics: ResMut<PhysicsConfig>,
+) {
+    let Some(handle) = handle else {
+        return;
+    };
+    let changed = events
+        .read()
+        .any(|e| e.is_loaded_with_dependencies(&handle.0) || e.is_modified(&handle.0));
+    if let Some(config) = configs.get(&handle.0).filter(|_| changed) {
+        info!("physics config: {config:?}");
+        *physics = *config;
+    }
+}
@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+
+use crate::components::{Player, RemotePlayer, TickInput, ViewTick};
+
+/// Feeds each remote player the next input its client sent, exactly one per
+/// tick so the client can predict the outcome, along with the tick the client
+/// was looking at. A late input repeats the last one without its shot.
+pub fn apply_remote_inputs(
+    mut commands: Commands,
+    mut q: Query<(Entity, &mut Player, &mut RemotePlayer, &mut TickInput)>,
+) {
+    for (entity, mut player, mut remote, mut input) in &mut q {
+        // The queue is kept short on receipt, so a burst is caught up on
+        // rather than lagged behind for good
+        match remote.queue.pop_front() {
+            Some(sent) => {
+                remote.input = sent.input;
+                remote.ack = sent.seq;
+                commands.entity(entity).insert(ViewTick(sent.view_tick));
+            }
+            None => remote.input.fire = false,
+        }
+        *input = remote.input;
+        player.yaw = input.yaw;
+        player.pitch = input.pitch;
+    }
+}
@@ -0,0 +1,89 @@
+use bevy::prelude::*;
+
+use crate::components::{
+    AppMode, Dead, Health, NetClient, NetMessage, Player, PlayerScore, Predicted,
+    PreviousTranslation, Scoreboard, Velocity,
+};
+use crate::constants::MAX_PLAYER_COUNT;
+use crate::events::ServerMessage;
+
+type SnapshotQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut Player,
+        &'static mut Transform,
+        &'static mut PreviousTranslation,
+        &'static mut Velocity,
+        &'static mut Health,
+        Has<Dead>,
+        Has<Predicted>,
+    ),
+>;
+
+/// Moves every player but our predicted one to the newest snapshot from the
+/// server, and follows its health, score and match end.
+pub fn apply_snapshots(
+    mut commands: Commands,
+    mut client: ResMut<NetClient>,
+    mode: Res<State<AppMode>>,
+    mut next_mode: ResMut<NextState<AppMode>>,
+    mut scoreboard: ResMut<Scoreboard>,
+    mut ev_messages: EventReader<ServerMessage>,
+    mut q: SnapshotQuery,
+) {
+    let newest = ev_messages
+        .read()
+        .filter_map(|ServerMessage(m)| match m {
+            NetMessage::Snapshot(snapshot) => Some(snapshot),
+            _ => None,
+        })
+        .max_by_key(|snapshot| snapshot.tick);
+    // Datagrams can arrive out of order
+    let Some(snapshot) = newest.filter(|s| s.tick > client.tick) else {
+        return;
+    };
+    client.tick = snapshot.tick;
+
+    for (entity, mut player, mut tf, mut previous, mut vel, mut health, dead, predicted) in &mut q {
+        let Some(state) = snapshot.players.iter().find(|s| s.id == player.id) else {
+            continue;
+        };
+        health.0 = state.health;
+        if !predicted {
+            // Blend from where we were, unless respawning across the arena
+            previous.0 = if dead && !state.dead {
+                state.translation
+            } else {
+                tf.translation
+            };
+            tf.translation = state.translation;
+            vel.0 = state.velocity;
+            player.yaw = state.yaw;
+            player.pitch = state.pitch;
+        }
+        match (dead, state.dead) {
+            (false, true) => {
+                commands.entity(entity).insert(Dead {
+                    respawn: Timer::default(),
+                });
+            }
+            (true, false) => {
+                commands.entity(entity).remove::<Dead>();
+            }
+            _ => {}
+        }
+    }
+
+    *scoreboard = snapshot.scoreboard.clone();
+    // Scores are looked up by player id, so keep one per slot whatever came
+    scoreboard
+        .players
+        .resize(MAX_PLAYER_COUNT, PlayerScore::default());
+    match (snapshot.match_over, mode.get()) {
+        (true, AppMode::Playing) => next_mode.set(AppMode::MatchOver),
+        (false, AppMode::MatchOver) => next_mode.set(AppMode::Playing),
+        _ => {}
+    }
+}
@@ -0,0 +1,47 @@
+use bevy::prelude::*;
+
+use crate::components::{Bot, InputAssignment, MatchRng, Player, RemotePlayer, SettingsRes};
+
+type SlotQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Player,
+        &'static InputAssignment,
+        Has<RemotePlayer>,
+        Option<&'static mut Bot>,
+    ),
+>;
+
+/// Hands every slot nobody has joined to the bot difficulty chosen in its
+/// settings, and takes it back as soon as a device or a remote player
+/// claims the slot.
+pub fn assign_bots(
+    mut commands: Commands,
+    settings: Res<SettingsRes>,
+    rng: Res<MatchRng>,
+    mut q: SlotQuery,
+) {
+    for (entity, player, assignment, remote, bot) in &mut q {
+        let difficulty = settings
+            .player(player.id)
+            .bot
+            .filter(|_| assignment.is_empty() && !remote);
+        match (difficulty, bot) {
+            (Some(d), Some(mut bot)) => {
+                if bot.profile != d.profile() {
+                    bot.profile = d.profile();
+                }
+            }
+            (Some(d), None) => {
+                let seed = rng.seed.wrapping_add(player.id as u64);
+                commands.entity(entity).insert(Bot::new(d.profile(), seed));
+            }
+            (None, Some(_)) => {
+                commands.entity(entity).remove::<Bot>();
+            }
+            (None, None) => {}
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+
+use crate::components::{
+    ActionBindings, InputAssignment, InputDevice, Player, PlayerCount, RemotePlayer, Spectator,
+};
+
+/// Hands unclaimed devices to the first player without a device of that kind,
+/// growing `PlayerCount` as players join (mirrors SDL woodeneye-008).
+pub fn assign_input_devices(
+    bindings: Res<ActionBindings>,
+    kb: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    gamepads: Query<(Entity, &Gamepad)>,
+    mut q_players: Query<(&Player, &mut InputAssignment), Without<RemotePlayer>>,
+    q_spectators: Query<&InputAssignment, (With<Spectator>, Without<Player>)>,
+    mut player_count: ResMut<PlayerCount>,
+) {
+    // Release gamepads that have been disconnected
+    for (_, mut assignment) in &mut q_players {
+        if assignment.gamepad.is_some_and(|e| !gamepads.contains(e)) {
+            assignment.gamepad = None;
+        }
+    }
+
+    let mut pressed = Vec::new();
+    if mouse_buttons.get_just_pressed().next().is_some() {
+        pressed.push(InputDevice::Mouse);
+    }
+    for (k, table) in bindings.keyboards.iter().enumerate() {
+        if kb.any_just_pressed(table.keys()) {
+            pressed.push(InputDevice::Keyboard(k));
+        }
+    }
+    for (entity, gamepad) in &gamepads {
+        if gamepad.get_just_pressed().next().is_some() {
+            pressed.push(InputDevice::Gamepad(entity));
+        }
+    }
+
+    for device in pressed {
+        if q_players.iter().any(|(_, a)| a.owns(device))
+            || q_spectators.iter().any(|a| a.owns(device))
+        {
+            continue;
+        }
+        let mut players: Vec<_> = q_players.iter_mut().collect();
+        players.sort_by_key(|(p, _)| p.id);
+        if let Some((p, assignment)) = players.iter_mut().find(|(_, a)| !a.has_kind(device)) {
+            assignment.claim(device);
+            player_count.0 = player_count.0.max(p.id + 1);
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use bevy::prelude::*;
+
+use crate::components::{AppMode, MatchRules, RollbackSession, Scoreboard, SimTick};
+
+/// Advances the match clock by one tick and ends the match once a limit is
+/// reached. A rollback peer holds the match there instead, until the peers'
+/// inputs confirm it ended.
+pub fn check_match_end(
+    fixed: Res<Time<Fixed>>,
+    rules: Res<MatchRules>,
+    tick: Res<SimTick>,
+    session: Option<ResMut<RollbackSession>>,
+    mut scoreboard: ResMut<Scoreboard>,
+    mut next_mode: ResMut<NextState<AppMode>>,
+) {
+    scoreboard.ticks += 1;
+    let score_reached = rules
+        .score_limit
+        .is_some_and(|limit| scoreboard.players.iter().any(|s| s.kills >= limit));
+    let time_up = rules
+        .time_limit
+        .is_some_and(|limit| scoreboard.elapsed(fixed.timestep()) >= limit);
+    if !(score_reached || time_up) {
+        return;
+    }
+    match session {
+        Some(mut session) => session.ending = Some(tick.0),
+        None => next_mode.set(AppMode::MatchOver),
+    }
+}
@@ -1,23 +1,22 @@
 use bevy::prelude::*;
 
-use crate::components::{AppMode, GameMode, SettingsRes};
+use crate::components::{PlayerCamera, SettingsRes};
 
+/// Marks the camera's forward axis, which is the ray `handle_shooting` fires
+/// along from the same eye point.
 pub fn draw_crosshair_gizmos(
-    mode: Res<GameMode>,
     settings: Res<SettingsRes>,
     mut gizmos: Gizmos,
-    cams: Query<(&Transform, &Camera)>,
+    cams: Query<(&Transform, &Camera, &PlayerCamera)>,
 ) {
-    if !matches!(mode.0, AppMode::Playing) {
-        return;
-    }
-    for (tf, cam) in &cams {
+    for (tf, cam, cam_tag) in &cams {
         if !cam.is_active {
             continue;
         }
+        let half = settings.player(cam_tag.player_id).crosshair_half;
         let origin = tf.translation + tf.forward() * 2.0;
-        let right = tf.right() * settings.crosshair_half;
-        let up = tf.up() * settings.crosshair_half;
+        let right = tf.right() * half;
+        let up = tf.up() * half;
         gizmos.line(origin - right, origin + right, Color::WHITE);
         gizmos.line(origin - up, origin + up, Color::WHITE);
     }
@@ -1,24 +1,29 @@
 use bevy::prelude::*;
 
-use crate::components::{AppMode, Edges, GameMode, Player};
+use crate::components::{Arena, Dead, Edges, MapColors, Player, PreviousTranslation};
 
 pub fn draw_world_gizmos(
-    mode: Res<GameMode>,
     edges: Res<Edges>,
-    q_players: Query<(&Transform, &Player)>,
+    arena: Res<Arena>,
+    colors: Res<MapColors>,
+    fixed_time: Res<Time<Fixed>>,
+    q_players: Query<(&Transform, &PreviousTranslation, &Player), Without<Dead>>,
     mut gizmos: Gizmos,
 ) {
-    if !matches!(mode.0, AppMode::Playing) {
-        return;
-    }
-    let edge_color = Color::srgb(0.25, 0.25, 0.25);
     for (a, b) in &edges.0 {
-        gizmos.line(*a, *b, edge_color);
+        gizmos.line(*a, *b, colors.edges);
+    }
+    for obstacle in &arena.obstacles {
+        gizmos.cuboid(
+            Transform::from_translation(obstacle.center()).with_scale(obstacle.size()),
+            colors.obstacles,
+        );
     }
 
-    for (tf, p) in &q_players {
-        let feet = tf.translation + Vec3::Y * (p.radius - p.height);
-        let head = tf.translation;
+    let alpha = fixed_time.overstep_fraction();
+    for (tf, previous, p) in &q_players {
+        let head = previous.lerp(tf.translation, alpha);
+        let feet = head + Vec3::Y * (p.radius - p.height);
         gizmos.line(feet, head, p.color);
         gizmos.sphere(feet, p.radius, p.color);
         gizmos.sphere(head, p.radius, p.color);
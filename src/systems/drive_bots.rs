@@ -0,0 +1,148 @@
+use std::f32::consts::{PI, TAU};
+
+use bevy::prelude::*;
+use rand::Rng;
+use rand::rngs::StdRng;
+
+use crate::components::{Arena, Bot, Dead, Player, TickInput};
+use crate::helpers::{eye_position, line_of_sight, look_angles, look_direction};
+
+/// Cosine of the half-angle a bot notices opponents in; behind it is unseen.
+const VIEW_COS: f32 = 0.0;
+/// Fires once the aim is within this many radians of the aim point.
+const ON_TARGET: f32 = 0.03;
+/// A new wander goal is picked within this distance or after `GOAL_TIMEOUT`
+/// seconds, in case something is in the way.
+const GOAL_RADIUS: f32 = 1.5;
+const GOAL_TIMEOUT: f32 = 6.0;
+/// Mean seconds between changes of strafe direction while fighting.
+const STRAFE_TIME: f32 = 1.2;
+
+type LivingQuery<'w, 's> = Query<'w, 's, (&'static Player, &'static Transform), Without<Dead>>;
+
+type BotQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut Player,
+        &'static Transform,
+        &'static mut Bot,
+        &'static mut TickInput,
+    ),
+    Without<Dead>,
+>;
+
+fn wrap_angle(a: f32) -> f32 {
+    (a + PI).rem_euclid(TAU) - PI
+}
+
+/// Plays every player with a `Bot`: wanders the arena, turns toward the
+/// nearest opponent in sight once its reaction time has passed and fires
+/// through `TickInput` like a human would.
+pub fn drive_bots(
+    time: Res<Time>,
+    arena: Res<Arena>,
+    mut queries: ParamSet<(LivingQuery, BotQuery)>,
+) {
+    let dt = time.delta_secs();
+    let opponents: Vec<(usize, Vec3)> = queries
+        .p0()
+        .iter()
+        .map(|(p, tf)| (p.id, tf.translation))
+        .collect();
+    for (mut player, tf, mut bot, mut input) in &mut queries.p1() {
+        let bot = &mut *bot;
+        let profile = bot.profile;
+        let eye = eye_position(tf.translation);
+        let facing = look_direction(player.yaw, 0.0);
+        let roll_offset = |rng: &mut StdRng| {
+            let e = profile.aim_error;
+            if e > 0.0 {
+                Vec2::new(rng.gen_range(-e..e), rng.gen_range(-e..e))
+            } else {
+                Vec2::ZERO
+            }
+        };
+
+        // Keep tracking the current target while in sight, else take the
+        // nearest opponent in view
+        let tracked = bot.target;
+        let seen = opponents
+            .iter()
+            .filter(|(id, head)| {
+                *id != player.id
+                    && (tracked == Some(*id)
+                        || facing.dot((*head - eye).normalize_or_zero()) > VIEW_COS)
+                    && line_of_sight(&arena.obstacles, eye, *head)
+            })
+            .min_by(|a, b| {
+                let key = |(id, head): &(usize, Vec3)| (tracked != Some(*id), head.distance(eye));
+                let (a, b) = (key(a), key(b));
+                a.0.cmp(&b.0).then(a.1.total_cmp(&b.1))
+            })
+            .copied();
+        match seen {
+            Some((id, _)) if bot.target == Some(id) => bot.seen_for += dt,
+            Some((id, _)) => {
+                bot.target = Some(id);
+                bot.seen_for = 0.0;
+                bot.aim_offset = roll_offset(&mut bot.rng);
+            }
+            None => bot.target = None,
+        }
+        bot.cooldown = (bot.cooldown - dt).max(0.0);
+
+        let engaged = seen
+            .filter(|_| bot.seen_for >= profile.reaction_time)
+            .map(|(_, head)| head);
+        let (want_yaw, want_pitch, movement) = if let Some(head) = engaged {
+            if bot.rng.gen_bool((dt / STRAFE_TIME).clamp(0.0, 1.0) as f64) {
+                bot.strafe = -bot.strafe;
+            }
+            let (yaw, pitch) = look_angles(head - eye);
+            (
+                yaw + bot.aim_offset.x,
+                pitch + bot.aim_offset.y,
+                Vec2::new(bot.strafe, 0.0),
+            )
+        } else {
+            let arrived = bot.goal.is_none_or(|g| {
+                Vec2::new(g.x - tf.translation.x, g.z - tf.translation.z).length() < GOAL_RADIUS
+            });
+            bot.goal_time += dt;
+            if arrived || bot.goal_time > GOAL_TIMEOUT {
+                bot.goal_time = 0.0;
+                // An arena too small to walk in has only its centre
+                let extent = (arena.half_extent - player.radius).max(0.0);
+                bot.goal = Some(Vec3::new(
+                    bot.rng.gen_range(-extent..=extent),
+                    arena.floor(),
+                    bot.rng.gen_range(-extent..=extent),
+                ));
+            }
+            let goal = bot.goal.unwrap_or(tf.translation);
+            let (yaw, _) = look_angles(goal - tf.translation);
+            (yaw, 0.0, Vec2::new(0.0, -1.0))
+        };
+
+        let max_turn = profile.turn_speed * dt;
+        let yaw_error = wrap_angle(want_yaw - player.yaw);
+        let pitch_error = want_pitch - player.pitch;
+        player.yaw = wrap_angle(player.yaw + yaw_error.clamp(-max_turn, max_turn));
+        player.pitch = (player.pitch + pitch_error.clamp(-max_turn, max_turn)).clamp(-1.6, 1.6);
+
+        let aimed = yaw_error.abs().max(pitch_error.abs()) <= max_turn + ON_TARGET;
+        let fire = engaged.is_some() && aimed && bot.cooldown <= 0.0;
+        if fire {
+            bot.cooldown = profile.fire_interval;
+            bot.aim_offset = roll_offset(&mut bot.rng);
+        }
+        *input = TickInput {
+            movement,
+            jump: false,
+            fire,
+            yaw: player.yaw,
+            pitch: player.pitch,
+        };
+    }
+}
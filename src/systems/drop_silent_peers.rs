@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+
+use crate::components::RollbackSession;
+use crate::constants::NET_TIMEOUT;
+
+/// Plays on without peers that went quiet, rather than waiting for their
+/// inputs forever.
+pub fn drop_silent_peers(mut session: ResMut<RollbackSession>, real: Res<Time<Real>>) {
+    for player in session.drop_silent(real.elapsed_secs_f64()) {
+        warn!(
+            "no word from player {} for {NET_TIMEOUT} seconds; playing on without them",
+            player + 1
+        );
+    }
+}
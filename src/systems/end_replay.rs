@@ -0,0 +1,11 @@
+use bevy::prelude::*;
+
+use crate::components::{AppMode, Demo, SimTick};
+
+/// Shows the final standings once every recorded tick has been played.
+pub fn end_replay(demo: Res<Demo>, tick: Res<SimTick>, mut next_mode: ResMut<NextState<AppMode>>) {
+    if tick.0 >= demo.ticks.len() as u64 {
+        info!("end of replay after {} ticks", tick.0);
+        next_mode.set(AppMode::MatchOver);
+    }
+}
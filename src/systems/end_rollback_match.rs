@@ -0,0 +1,17 @@
+use bevy::prelude::*;
+
+use crate::components::{AppMode, RollbackSession};
+
+/// Ends a rollback match once every peer's inputs confirm the ticks that
+/// ended it, so every peer ends on the same tick with the same scores.
+pub fn end_rollback_match(
+    mut session: ResMut<RollbackSession>,
+    mut next_mode: ResMut<NextState<AppMode>>,
+) {
+    if let Some(tick) = session.ending
+        && session.confirmed(tick)
+    {
+        session.ending = None;
+        next_mode.set(AppMode::MatchOver);
+    }
+}
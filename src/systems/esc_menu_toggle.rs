@@ -1,12 +1,13 @@
 use bevy::prelude::*;
 
-use crate::components::{AppMode, GameMode};
+use crate::components::{Action, ActionState, AppMode};
 
-pub fn esc_menu_toggle(kb: Res<ButtonInput<KeyCode>>, mut mode: ResMut<GameMode>) {
-    if kb.just_pressed(KeyCode::Escape) {
-        mode.0 = match mode.0 {
-            AppMode::Playing => AppMode::Menu,
-            _ => AppMode::Playing,
-        };
+pub fn esc_menu_toggle(
+    actions: Res<ActionState>,
+    mode: Res<State<AppMode>>,
+    mut next_mode: ResMut<NextState<AppMode>>,
+) {
+    if actions.just_pressed(Action::Menu) {
+        next_mode.set(mode.get().back());
     }
 }
@@ -0,0 +1,106 @@
+use bevy::prelude::*;
+use bevy::ui::RelativeCursorPosition;
+
+use crate::components::{
+    Action, ActionState, AppMode, BotDifficulty, MenuEntry, MenuFocus, MenuItem, PlayerCount,
+    SettingsRes, SettingsSelection, SliderTrack,
+};
+use crate::constants::MAX_PLAYER_COUNT;
+use crate::helpers::{set_slider, step_slider};
+
+/// Drives the pause and settings pages from keyboard, gamepad (via the
+/// global `Menu*` actions) and mouse.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_menu_input(
+    mode: Res<State<AppMode>>,
+    mut next_mode: ResMut<NextState<AppMode>>,
+    actions: Res<ActionState>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mut focus: ResMut<MenuFocus>,
+    mut selection: ResMut<SettingsSelection>,
+    mut settings: ResMut<SettingsRes>,
+    mut player_count: ResMut<PlayerCount>,
+    mut exit: EventWriter<AppExit>,
+    q_entries: Query<(&MenuEntry, &Interaction)>,
+    q_tracks: Query<(&SliderTrack, &RelativeCursorPosition)>,
+) {
+    let page = *mode.get();
+
+    let mut entries: Vec<(&MenuEntry, &Interaction)> =
+        q_entries.iter().filter(|(e, _)| e.page == page).collect();
+    entries.sort_by_key(|(e, _)| e.index);
+    if entries.is_empty() {
+        return;
+    }
+    let count = entries.len();
+
+    if actions.just_pressed(Action::MenuUp) {
+        focus.0 = (focus.0 + count - 1) % count;
+    }
+    if actions.just_pressed(Action::MenuDown) {
+        focus.0 = (focus.0 + 1) % count;
+    }
+    let mut clicked = None;
+    for (entry, interaction) in &entries {
+        match interaction {
+            Interaction::Hovered => focus.0 = entry.index,
+            Interaction::Pressed if mouse_buttons.just_pressed(MouseButton::Left) => {
+                focus.0 = entry.index;
+                clicked = Some(entry.item);
+            }
+            _ => {}
+        }
+    }
+    focus.0 = focus.0.min(count - 1);
+    let focused = entries[focus.0].0.item;
+
+    if mouse_buttons.pressed(MouseButton::Left) {
+        for (track, cursor) in &q_tracks {
+            if let Some(pos) = cursor.normalized.filter(|_| cursor.mouse_over()) {
+                set_slider(settings.player_mut(selection.0), track.0, pos.x);
+            }
+        }
+    }
+
+    let steps = actions.just_pressed(Action::MenuRight) as i32
+        - actions.just_pressed(Action::MenuLeft) as i32;
+    if steps != 0 {
+        match focused {
+            MenuItem::PlayerCount => {
+                player_count.0 =
+                    (player_count.0 as i32 + steps).clamp(1, MAX_PLAYER_COUNT as i32) as usize;
+            }
+            MenuItem::Player => {
+                let n = MAX_PLAYER_COUNT as i32;
+                selection.0 = (selection.0 as i32 + steps).rem_euclid(n) as usize;
+            }
+            MenuItem::Bot => {
+                let player = settings.player_mut(selection.0);
+                player.bot = BotDifficulty::cycle(player.bot, steps);
+            }
+            item => step_slider(settings.player_mut(selection.0), item, steps),
+        }
+    }
+
+    let activated = clicked.or(actions.just_pressed(Action::MenuSelect).then_some(focused));
+    match activated {
+        Some(MenuItem::Resume) => next_mode.set(AppMode::Playing),
+        Some(MenuItem::Settings) => next_mode.set(AppMode::Settings),
+        Some(MenuItem::Bindings) => next_mode.set(AppMode::Bindings),
+        Some(MenuItem::Back) => next_mode.set(page.back()),
+        Some(MenuItem::Quit) => {
+            exit.write(AppExit::Success);
+        }
+        Some(MenuItem::PlayerCount) => {
+            player_count.0 = player_count.0 % MAX_PLAYER_COUNT + 1;
+        }
+        Some(MenuItem::Player) => {
+            selection.0 = (selection.0 + 1) % MAX_PLAYER_COUNT;
+        }
+        Some(MenuItem::Bot) => {
+            let player = settings.player_mut(selection.0);
+            player.bot = BotDifficulty::cycle(player.bot, 1);
+        }
+        _ => {}
+    }
+}
@@ -1,25 +1,27 @@
 use bevy::prelude::*;
 
-use crate::components::{AppMode, GameMode, SettingsRes};
+use crate::components::{Action, ActionState, MenuItem, SettingsRes, SettingsSelection};
+use crate::constants::MAX_PLAYER_COUNT;
+use crate::helpers::step_slider;
 
+/// Shortcut keys on the settings page, alongside the menu widgets.
 pub fn handle_settings_input(
-    mode: Res<GameMode>,
-    kb: Res<ButtonInput<KeyCode>>,
+    actions: Res<ActionState>,
+    mut selection: ResMut<SettingsSelection>,
     mut settings: ResMut<SettingsRes>,
 ) {
-    if !matches!(mode.0, AppMode::Settings) {
-        return;
+    if actions.just_pressed(Action::NextPlayer) {
+        selection.0 = (selection.0 + 1) % MAX_PLAYER_COUNT;
     }
-    if kb.just_pressed(KeyCode::BracketLeft) {
-        settings.crosshair_half = (settings.crosshair_half - 0.02).max(0.02);
-    }
-    if kb.just_pressed(KeyCode::BracketRight) {
-        settings.crosshair_half = (settings.crosshair_half + 0.02).min(1.0);
-    }
-    if kb.just_pressed(KeyCode::Minus) {
-        settings.sensitivity = (settings.sensitivity - 0.0005).max(0.0001);
-    }
-    if kb.just_pressed(KeyCode::Equal) {
-        settings.sensitivity = (settings.sensitivity + 0.0005).min(0.02);
+    let shortcuts = [
+        (Action::CrosshairShrink, MenuItem::Crosshair, -1),
+        (Action::CrosshairGrow, MenuItem::Crosshair, 1),
+        (Action::SensitivityDown, MenuItem::Sensitivity, -1),
+        (Action::SensitivityUp, MenuItem::Sensitivity, 1),
+    ];
+    for (action, item, steps) in shortcuts {
+        if actions.just_pressed(action) {
+            step_slider(settings.player_mut(selection.0), item, steps);
+        }
     }
 }
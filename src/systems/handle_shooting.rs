@@ -1,56 +1,144 @@
 use bevy::prelude::*;
-use rand::Rng;
 
-use crate::components::{AppMode, GameMode, Player};
-use crate::constants::MAP_BOX_SCALE;
+use crate::components::{
+    Arena, Dead, Health, MatchRules, Player, PositionHistory, SimTick, TickInput, ViewTick,
+};
+use crate::events::{HitZone, PlayerHit, PlayerKilled, ShotFired};
+use crate::helpers::{eye_position, ray_obstacle_distance};
 
+type ShooterQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Transform,
+        &'static Player,
+        &'static mut TickInput,
+        Option<&'static ViewTick>,
+        Has<Dead>,
+    ),
+>;
+
+type TargetQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Transform,
+        &'static Player,
+        &'static mut Health,
+        Option<&'static PositionHistory>,
+    ),
+    Without<Dead>,
+>;
+
+/// Fires every latched shot. A shooter with a `ViewTick` saw the world late,
+/// so targets are rewound to where they were on that tick, as far back as
+/// their `PositionHistory` reaches.
+#[allow(clippy::too_many_arguments)]
 pub fn handle_shooting(
-    mode: Res<GameMode>,
-    mouse_buttons: Res<ButtonInput<MouseButton>>,
-    mut q_players: Query<(&mut Transform, &mut Player)>,
+    mut commands: Commands,
+    rules: Res<MatchRules>,
+    arena: Res<Arena>,
+    tick: Res<SimTick>,
+    mut q_shooters: ShooterQuery,
+    mut q_targets: TargetQuery,
+    mut ev_shot: EventWriter<ShotFired>,
+    mut ev_hit: EventWriter<PlayerHit>,
+    mut ev_killed: EventWriter<PlayerKilled>,
 ) {
-    if !matches!(mode.0, AppMode::Playing) {
-        return;
-    }
-    if !mouse_buttons.just_pressed(MouseButton::Left) {
+    // Every latched shot is consumed, even by the dead
+    let mut fired: Vec<(ShotFired, Option<u64>)> = q_shooters
+        .iter_mut()
+        .filter_map(|(tf, p, mut input, view, dead)| {
+            let fired = std::mem::take(&mut input.fire);
+            (fired && !dead).then(|| {
+                let shot = ShotFired {
+                    shooter: p.id,
+                    origin: eye_position(tf.translation),
+                    direction: input.forward().normalize(),
+                    tick: tick.0,
+                };
+                (shot, view.map(|v| v.0))
+            })
+        })
+        .collect();
+    // Stable order so simultaneous kills credit the same shooter every run
+    fired.sort_by_key(|(s, _)| s.shooter);
+    if fired.is_empty() {
         return;
     }
+    let (shots, views): (Vec<ShotFired>, Vec<Option<u64>>) = fired.into_iter().unzip();
+    let walls: Vec<Option<f32>> = shots
+        .iter()
+        .map(|s| ray_obstacle_distance(&arena.obstacles, s.origin, s.direction))
+        .collect();
 
-    let (origin, yaw, pitch, shooter_id) = {
-        let (shooter_tf, shooter) = q_players.iter().find(|(_, p)| p.id == 0).unwrap();
-        (shooter_tf.translation, shooter.yaw, shooter.pitch, shooter.id)
-    };
-
-    let (sin_yaw, cos_yaw) = yaw.sin_cos();
-    let (sin_pitch, cos_pitch) = pitch.sin_cos();
-    let dir = Vec3::new(-sin_yaw * cos_pitch, sin_pitch, -cos_yaw * cos_pitch).normalize();
-
-    for (mut tf, mut target) in &mut q_players {
-        if target.id == shooter_id {
-            continue;
-        }
-        let offset = tf.translation - origin;
-        let mut hit_count = 0;
-        for j in 0..2 {
-            let dy = offset.y + if j == 0 { 0.0 } else { target.radius - target.height };
-            let d = Vec3::new(offset.x, dy, offset.z);
-            let vd = dir.dot(d);
-            if vd < 0.0 {
+    for (entity, tf, target, mut health, history) in &mut q_targets {
+        for ((shot, view), wall) in shots.iter().zip(&views).zip(&walls) {
+            if target.id == shot.shooter || health.0 <= 0.0 {
                 continue;
             }
-            let dd = d.length_squared();
-            let rr = target.radius * target.radius;
-            let vv = 1.0;
-            if vd * vd >= vv * (dd - rr) {
-                hit_count += 1;
+            let (origin, dir) = (shot.origin, shot.direction);
+            let seen_at = view
+                .zip(history)
+                .and_then(|(tick, history)| history.at(tick))
+                .unwrap_or(tf.translation);
+            let offset = seen_at - origin;
+            // Sphere 0 is the head, sphere 1 the feet; keep the first one struck
+            let mut hit = None;
+            for j in 0..2 {
+                let dy = offset.y
+                    + if j == 0 {
+                        0.0
+                    } else {
+                        target.radius - target.height
+                    };
+                let d = Vec3::new(offset.x, dy, offset.z);
+                let vd = dir.dot(d);
+                if vd < 0.0 {
+                    continue;
+                }
+                let dd = d.length_squared();
+                let rr = target.radius * target.radius;
+                let vv = 1.0;
+                if hit.is_none() && vd * vd >= vv * (dd - rr) {
+                    let entry = vd - (vd * vd - (dd - rr)).sqrt();
+                    let zone = if j == 0 { HitZone::Head } else { HitZone::Feet };
+                    hit = Some((zone, origin + dir * entry));
+                }
+            }
+            let Some((zone, location)) = hit else {
+                continue;
+            };
+            if wall.is_some_and(|w| w < location.distance(origin)) {
+                continue;
+            }
+            let damage = match zone {
+                HitZone::Head => rules.head_damage,
+                HitZone::Feet => rules.body_damage,
+            };
+            health.0 -= damage;
+            ev_hit.write(PlayerHit {
+                shooter: shot.shooter,
+                target: target.id,
+                location,
+                zone,
+                damage,
+                tick: tick.0,
+            });
+            if health.0 <= 0.0 {
+                ev_killed.write(PlayerKilled {
+                    shooter: shot.shooter,
+                    target: target.id,
+                    location,
+                    zone,
+                    tick: tick.0,
+                });
+                commands.entity(entity).insert(Dead {
+                    respawn: Timer::from_seconds(rules.respawn_delay, TimerMode::Once),
+                });
             }
-        }
-        if hit_count > 0 {
-            let scale = MAP_BOX_SCALE as f32;
-            let mut rng = rand::thread_rng();
-            tf.translation.x = scale * (rng.r#gen::<f32>() - 0.5);
-            tf.translation.y = scale * (rng.r#gen::<f32>() - 0.5);
-            tf.translation.z = scale * (rng.r#gen::<f32>() - 0.5);
         }
     }
+    ev_shot.write_batch(shots);
 }
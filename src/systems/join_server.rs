@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+
+use crate::components::{
+    InputAssignment, MapPath, NetClient, NetMessage, Player, PlayerCamera, Predicted, Replicated,
+};
+use crate::events::ServerMessage;
+
+/// Takes the slot the server seated us in: the local devices and first camera
+/// move from player 1 to it, where we predict our own movement, and every
+/// other player becomes a mirror of the server's. Exits when the server turns us away.
+#[allow(clippy::too_many_arguments)]
+pub fn join_server(
+    mut commands: Commands,
+    mut client: ResMut<NetClient>,
+    mut fixed: ResMut<Time<Fixed>>,
+    map: Option<Res<MapPath>>,
+    mut ev_messages: EventReader<ServerMessage>,
+    mut q_players: Query<(Entity, &Player, &mut InputAssignment)>,
+    mut q_cameras: Query<&mut PlayerCamera>,
+    mut exit: EventWriter<AppExit>,
+) {
+    for ServerMessage(message) in ev_messages.read() {
+        match message {
+            NetMessage::Welcome {
+                player_id,
+                tick_rate,
+                map: server_map,
+            } if client.player_id.is_none() => {
+                let id = *player_id;
+                client.player_id = Some(id);
+                fixed.set_timestep_hz(*tick_rate);
+                info!("joined the server as player {}", id + 1);
+                if map.as_ref().is_some_and(|m| m.0 != *server_map) {
+                    warn!("the server plays {server_map}; pass --map {server_map} to match it");
+                }
+
+                let mut local = InputAssignment::default();
+                for (_, player, mut assignment) in &mut q_players {
+                    if player.id == 0 {
+                        local = std::mem::take(&mut *assignment);
+                    }
+                }
+                for (entity, player, mut assignment) in &mut q_players {
+                    if player.id == id {
+                        *assignment = std::mem::take(&mut local);
+                        commands
+                            .entity(entity)
+                            .remove::<Replicated>()
+                            .insert(Predicted::default());
+                    } else {
+                        commands.entity(entity).insert(Replicated);
+                    }
+                }
+                for mut camera in &mut q_cameras {
+                    if camera.player_id == 0 {
+                        camera.player_id = id;
+                    } else if camera.player_id == id {
+                        camera.player_id = 0;
+                    }
+                }
+            }
+            NetMessage::Rejected { reason } => {
+                error!("the server turned us away: {reason}");
+                exit.write(AppExit::error());
+            }
+            _ => {}
+        }
+    }
+}
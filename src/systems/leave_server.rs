@@ -0,0 +1,12 @@
+use bevy::prelude::*;
+
+use crate::components::{NetClient, NetMessage};
+use crate::helpers::send_message;
+
+/// Frees our slot on the server when the app exits, rather than leaving it
+/// to time out.
+pub fn leave_server(client: Res<NetClient>) {
+    if client.player_id.is_some() {
+        send_message(&client.socket, None, &NetMessage::Bye);
+    }
+}
@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+use crate::components::{MapHandle, MapPath};
+
+pub fn load_map(mut commands: Commands, asset_server: Res<AssetServer>, path: Res<MapPath>) {
+    commands.insert_resource(MapHandle(asset_server.load(&path.0)));
+}
@@ -0,0 +1,8 @@
+use bevy::prelude::*;
+
+use crate::components::PhysicsConfigHandle;
+use crate::constants::PHYSICS_CONFIG_PATH;
+
+pub fn load_physics_config(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(PhysicsConfigHandle(asset_server.load(PHYSICS_CONFIG_PATH)));
+}
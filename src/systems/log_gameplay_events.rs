@@ -0,0 +1,32 @@
+use bevy::prelude::*;
+
+use crate::components::SimTick;
+use crate::events::{PlayerHit, PlayerKilled, PlayerRespawned};
+
+/// Logs combat events once; ticks a rollback runs again emit theirs again,
+/// and those are skipped.
+pub fn log_gameplay_events(
+    tick: Res<SimTick>,
+    mut logged: Local<u64>,
+    mut ev_hit: EventReader<PlayerHit>,
+    mut ev_killed: EventReader<PlayerKilled>,
+    mut ev_respawned: EventReader<PlayerRespawned>,
+) {
+    let new = |t: u64| t >= *logged;
+    for e in ev_hit.read().filter(|e| new(e.tick)) {
+        debug!(
+            "player {} hit player {} ({:?}, {} damage)",
+            e.shooter, e.target, e.zone, e.damage
+        );
+    }
+    for e in ev_killed.read().filter(|e| new(e.tick)) {
+        info!(
+            "player {} killed player {} ({:?})",
+            e.shooter, e.target, e.zone
+        );
+    }
+    for e in ev_respawned.read().filter(|e| new(e.tick)) {
+        debug!("player {} respawned at {}", e.player, e.location);
+    }
+    *logged = tick.0;
+}
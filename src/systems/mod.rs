@@ -11,6 +11,60 @@ pub mod draw_crosshair_gizmos;
 pub mod esc_menu_toggle;
 pub mod update_cursor_visibility;
 pub mod handle_settings_input;
+pub mod assign_input_devices;
+pub mod update_action_state;
+pub mod rebind_actions;
+pub mod setup_rebind_screen;
+pub mod update_rebind_screen;
+pub mod save_settings;
+pub mod setup_menu;
+pub mod handle_menu_input;
+pub mod update_menu;
+pub mod show_menu_page;
+pub mod respawn_players;
+pub mod check_match_end;
+pub mod write_match_results;
+pub mod reset_match;
+pub mod setup_scoreboard;
+pub mod update_scoreboard;
+pub mod record_kills;
+pub mod log_gameplay_events;
+pub mod sample_tick_input;
+pub mod save_previous_translations;
+pub mod resolve_player_collisions;
+pub mod load_map;
+pub mod apply_map;
+pub mod load_physics_config;
+pub mod apply_physics_config;
+pub mod advance_sim_tick;
+pub mod apply_input_script;
+pub mod assign_bots;
+pub mod drive_bots;
+pub mod record_tick_inputs;
+pub mod write_demo;
+pub mod end_replay;
+pub mod apply_demo_setup;
+pub mod setup_spectator;
+pub mod update_spectators;
+pub mod receive_client_messages;
+pub mod apply_remote_inputs;
+pub mod send_snapshots;
+pub mod receive_server_messages;
+pub mod join_server;
+pub mod apply_snapshots;
+pub mod send_client_input;
+pub mod leave_server;
+pub mod predict_local_player;
+pub mod reconcile_prediction;
+pub mod record_position_history;
+pub mod save_rollback_state;
+pub mod apply_peer_inputs;
+pub mod send_peer_inputs;
+pub mod rollback_late_inputs;
+pub mod take_rollback_slot;
+pub mod apply_demo_view_ticks;
+pub mod end_rollback_match;
+pub mod drop_silent_peers;
 
 pub use setup_players::setup_players;
 pub use setup_cameras::setup_cameras;
@@ -25,3 +79,57 @@ pub use draw_crosshair_gizmos::draw_crosshair_gizmos;
 pub use esc_menu_toggle::esc_menu_toggle;
 pub use update_cursor_visibility::update_cursor_visibility;
 pub use handle_settings_input::handle_settings_input;
+pub use assign_input_devices::assign_input_devices;
+pub use update_action_state::update_action_state;
+pub use rebind_actions::rebind_actions;
+pub use setup_rebind_screen::setup_rebind_screen;
+pub use update_rebind_screen::update_rebind_screen;
+pub use save_settings::save_settings;
+pub use setup_menu::setup_menu;
+pub use handle_menu_input::handle_menu_input;
+pub use update_menu::update_menu;
+pub use show_menu_page::show_menu_page;
+pub use respawn_players::respawn_players;
+pub use check_match_end::check_match_end;
+pub use write_match_results::write_match_results;
+pub use reset_match::reset_match;
+pub use setup_scoreboard::setup_scoreboard;
+pub use update_scoreboard::update_scoreboard;
+pub use record_kills::record_kills;
+pub use log_gameplay_events::log_gameplay_events;
+pub use sample_tick_input::sample_tick_input;
+pub use save_previous_translations::save_previous_translations;
+pub use resolve_player_collisions::resolve_player_collisions;
+pub use load_map::load_map;
+pub use apply_map::apply_map;
+pub use load_physics_config::load_physics_config;
+pub use apply_physics_config::apply_physics_config;
+pub use advance_sim_tick::advance_sim_tick;
+pub use apply_input_script::apply_input_script;
+pub use assign_bots::assign_bots;
+pub use drive_bots::drive_bots;
+pub use record_tick_inputs::record_tick_inputs;
+pub use write_demo::write_demo;
+pub use end_replay::end_replay;
+pub use apply_demo_setup::apply_demo_setup;
+pub use setup_spectator::setup_spectator;
+pub use update_spectators::update_spectators;
+pub use receive_client_messages::receive_client_messages;
+pub use apply_remote_inputs::apply_remote_inputs;
+pub use send_snapshots::send_snapshots;
+pub use receive_server_messages::receive_server_messages;
+pub use join_server::join_server;
+pub use apply_snapshots::apply_snapshots;
+pub use send_client_input::send_client_input;
+pub use leave_server::leave_server;
+pub use predict_local_player::predict_local_player;
+pub use reconcile_prediction::reconcile_prediction;
+pub use record_position_history::record_position_history;
+pub use save_rollback_state::save_rollback_state;
+pub use apply_peer_inputs::apply_peer_inputs;
+pub use send_peer_inputs::send_peer_inputs;
+pub use rollback_late_inputs::rollback_late_inputs;
+pub use take_rollback_slot::take_rollback_slot;
+pub use apply_demo_view_ticks::apply_demo_view_ticks;
+pub use end_rollback_match::end_rollback_match;
+pub use drop_silent_peers::drop_silent_peers;
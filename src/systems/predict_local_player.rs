@@ -0,0 +1,61 @@
+use bevy::prelude::*;
+
+use crate::components::{
+    Arena, Dead, PhysicsConfig, Player, Predicted, PreviousTranslation, TickInput, Velocity,
+};
+use crate::constants::CORRECTION_RATE;
+use crate::helpers::{collide_with_players, step_player};
+
+type PredictedQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Player,
+        &'static TickInput,
+        &'static mut Transform,
+        &'static mut PreviousTranslation,
+        &'static mut Velocity,
+        &'static mut Predicted,
+        Has<Dead>,
+    ),
+>;
+
+type ReplicatedQuery<'w, 's> =
+    Query<'w, 's, (&'static Player, &'static Transform), (Without<Predicted>, Without<Dead>)>;
+
+/// Moves our player ahead of the server with the input just sent, through the
+/// same physics step the server runs, and fades out the last correction.
+/// Other players stay where the last snapshot put them; we are pushed off
+/// them as the server would, though not they off us.
+pub fn predict_local_player(
+    time: Res<Time>,
+    arena: Res<Arena>,
+    physics: Res<PhysicsConfig>,
+    mut q: PredictedQuery,
+    others: ReplicatedQuery,
+) {
+    let dt = time.delta_secs();
+    let others: Vec<_> = others.iter().map(|(p, tf)| (p, tf.translation)).collect();
+    for (player, input, mut tf, mut previous, mut vel, mut predicted, dead) in &mut q {
+        predicted.correction *= (-dt * CORRECTION_RATE).exp();
+        if dead {
+            continue;
+        }
+        previous.0 = tf.translation;
+        step_player(
+            &mut tf.translation,
+            &mut vel.0,
+            player,
+            input,
+            dt,
+            &arena,
+            &physics,
+        );
+        collide_with_players(
+            (player, &mut tf.translation, &mut vel.0, input.jump),
+            &others,
+            &arena,
+            physics.jump_velocity,
+        );
+    }
+}
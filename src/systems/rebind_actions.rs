@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+
+use crate::components::{Action, ActionBindings, ActionState, Binding, RebindState};
+use crate::constants::BINDINGS_PATH;
+use crate::helpers::save_config;
+
+/// Key bindings page: pick a table and action, press `MenuSelect`, then press
+/// the new key or button. Escape cancels a capture.
+pub fn rebind_actions(
+    actions: Res<ActionState>,
+    kb: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    gamepads: Query<&Gamepad>,
+    mut bindings: ResMut<ActionBindings>,
+    mut rebind: ResMut<RebindState>,
+) {
+    if rebind.capturing {
+        if kb.just_pressed(KeyCode::Escape) {
+            rebind.capturing = false;
+            return;
+        }
+        let pressed = kb
+            .get_just_pressed()
+            .next()
+            .map(|k| Binding::Key(*k))
+            .or_else(|| {
+                mouse_buttons
+                    .get_just_pressed()
+                    .next()
+                    .map(|b| Binding::Mouse(*b))
+            })
+            .or_else(|| {
+                gamepads
+                    .iter()
+                    .find_map(|pad| pad.get_just_pressed().next().map(|b| Binding::Gamepad(*b)))
+            });
+        if let Some(binding) = pressed {
+            let action = rebind.actions(&bindings)[rebind.action];
+            rebind.table_mut(&mut bindings).rebind(action, binding);
+            rebind.capturing = false;
+            if let Err(e) = save_config(BINDINGS_PATH, &*bindings) {
+                warn!("failed to save bindings to {BINDINGS_PATH}: {e}");
+            }
+        }
+        return;
+    }
+
+    let table_count = RebindState::tables(&bindings).len();
+    if actions.just_pressed(Action::MenuLeft) {
+        rebind.table = (rebind.table + table_count - 1) % table_count;
+        rebind.action = 0;
+    }
+    if actions.just_pressed(Action::MenuRight) {
+        rebind.table = (rebind.table + 1) % table_count;
+        rebind.action = 0;
+    }
+    let action_count = rebind.actions(&bindings).len();
+    if actions.just_pressed(Action::MenuUp) {
+        rebind.action = (rebind.action + action_count - 1) % action_count;
+    }
+    if actions.just_pressed(Action::MenuDown) {
+        rebind.action = (rebind.action + 1) % action_count;
+    }
+    if actions.just_pressed(Action::MenuSelect) {
+        rebind.capturing = true;
+    }
+}
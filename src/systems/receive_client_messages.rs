@@ -0,0 +1,104 @@
+use bevy::prelude::*;
+
+use crate::components::{
+    InputAssignment, MapPath, NetMessage, NetServer, Player, RemotePlayer, TickInput, ViewTick,
+};
+use crate::constants::{NET_PROTOCOL_VERSION, NET_TIMEOUT};
+use crate::helpers::{receive_messages, send_message};
+
+/// Seats clients that say hello in the first slot nobody plays here, queues
+/// their inputs, sanitized, for the coming ticks and frees the slots of clients that leave
+/// or go quiet.
+pub fn receive_client_messages(
+    mut commands: Commands,
+    server: Res<NetServer>,
+    real: Res<Time<Real>>,
+    fixed: Res<Time<Fixed>>,
+    map: Option<Res<MapPath>>,
+    mut q: Query<(Entity, &Player, &InputAssignment, Option<&mut RemotePlayer>)>,
+) {
+    let now = real.elapsed_secs_f64();
+    let mut slots: Vec<_> = q.iter_mut().collect();
+    slots.sort_by_key(|(_, p, ..)| p.id);
+    // Seats handed out this frame, before the commands inserting them apply
+    let mut seated: Vec<(std::net::SocketAddr, usize)> = Vec::new();
+
+    for (addr, message) in receive_messages(&server.socket) {
+        let remote = slots
+            .iter_mut()
+            .find(|(.., r)| r.as_ref().is_some_and(|r| r.addr == addr));
+        match message {
+            NetMessage::Hello { version } if version != NET_PROTOCOL_VERSION => {
+                let reason =
+                    format!("protocol version {version} (server speaks {NET_PROTOCOL_VERSION})");
+                send_message(&server.socket, Some(addr), &NetMessage::Rejected { reason });
+            }
+            NetMessage::Hello { .. } => {
+                let player_id = if let Some((_, player, _, Some(remote))) = remote {
+                    remote.last_heard = now;
+                    player.id
+                } else if let Some(&(_, id)) = seated.iter().find(|(a, _)| *a == addr) {
+                    id
+                } else if let Some((entity, player, ..)) = slots.iter().find(|(_, p, a, r)| {
+                    r.is_none() && a.is_empty() && !seated.iter().any(|(_, id)| *id == p.id)
+                }) {
+                    commands.entity(*entity).insert(RemotePlayer {
+                        addr,
+                        queue: default(),
+                        last_seq: 0,
+                        input: TickInput::default(),
+                        ack: 0,
+                        last_heard: now,
+                    });
+                    seated.push((addr, player.id));
+                    info!("{addr} joined as player {}", player.id + 1);
+                    player.id
+                } else {
+                    let reason = "the server is full".to_string();
+                    send_message(&server.socket, Some(addr), &NetMessage::Rejected { reason });
+                    continue;
+                };
+                let welcome = NetMessage::Welcome {
+                    player_id,
+                    tick_rate: 1.0 / fixed.timestep().as_secs_f64(),
+                    map: map.as_ref().map(|m| m.0.clone()).unwrap_or_default(),
+                };
+                send_message(&server.socket, Some(addr), &welcome);
+            }
+            NetMessage::Input(inputs) => {
+                if let Some((.., Some(remote))) = remote {
+                    remote.last_heard = now;
+                    // Resent inputs we already have are skipped
+                    for mut sent in inputs {
+                        if sent.seq > remote.last_seq {
+                            remote.last_seq = sent.seq;
+                            sent.input = sent.input.sanitized();
+                            remote.queue.push_back(sent);
+                        }
+                    }
+                    remote.trim_queue();
+                }
+            }
+            NetMessage::Bye => {
+                if let Some((entity, player, _, Some(_))) = remote {
+                    commands
+                        .entity(*entity)
+                        .remove::<(RemotePlayer, ViewTick)>();
+                    info!("{addr} left player {}", player.id + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for (entity, player, _, remote) in &slots {
+        if let Some(remote) = remote
+            && now - remote.last_heard > NET_TIMEOUT
+        {
+            commands
+                .entity(*entity)
+                .remove::<(RemotePlayer, ViewTick)>();
+            info!("{} timed out from player {}", remote.addr, player.id + 1);
+        }
+    }
+}
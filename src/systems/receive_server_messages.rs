@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+
+use crate::components::{NetClient, NetMessage};
+use crate::constants::{NET_HELLO_INTERVAL, NET_PROTOCOL_VERSION, NET_TIMEOUT};
+use crate::events::ServerMessage;
+use crate::helpers::{receive_messages, send_message};
+
+/// Says hello until the server seats us, then passes on whatever it sends and
+/// warns once when it goes quiet.
+pub fn receive_server_messages(
+    mut client: ResMut<NetClient>,
+    real: Res<Time<Real>>,
+    mut ev_messages: EventWriter<ServerMessage>,
+) {
+    let now = real.elapsed_secs_f64();
+    if client.player_id.is_none()
+        && client
+            .last_hello
+            .is_none_or(|t| now - t >= NET_HELLO_INTERVAL)
+    {
+        let hello = NetMessage::Hello {
+            version: NET_PROTOCOL_VERSION,
+        };
+        send_message(&client.socket, None, &hello);
+        if client.last_hello.is_none() {
+            client.last_heard = now;
+        }
+        client.last_hello = Some(now);
+    }
+
+    let messages = receive_messages(&client.socket);
+    if !messages.is_empty() {
+        client.last_heard = now;
+        client.timed_out = false;
+    }
+    ev_messages.write_batch(messages.into_iter().map(|(_, m)| ServerMessage(m)));
+
+    if !client.timed_out && now - client.last_heard > NET_TIMEOUT {
+        client.timed_out = true;
+        warn!("no word from the server for {NET_TIMEOUT} seconds");
+    }
+}
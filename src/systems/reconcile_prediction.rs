@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+
+use crate::components::{
+    Arena, Dead, NetMessage, PhysicsConfig, Player, Predicted, PreviousTranslation, Velocity,
+};
+use crate::constants::CORRECTION_SNAP_DISTANCE;
+use crate::events::ServerMessage;
+use crate::helpers::{collide_with_players, step_player};
+
+type PredictedQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Player,
+        &'static mut Transform,
+        &'static mut PreviousTranslation,
+        &'static mut Velocity,
+        &'static mut Predicted,
+    ),
+>;
+
+type ReplicatedQuery<'w, 's> =
+    Query<'w, 's, (&'static Player, &'static Transform), (Without<Predicted>, Without<Dead>)>;
+
+/// Restarts our prediction from the server's state in the newest snapshot,
+/// replaying the inputs it has not applied yet. Any disagreement becomes a
+/// camera correction that fades out, unless it is too large to glide over.
+/// Other players are held where the snapshot put them while replaying.
+pub fn reconcile_prediction(
+    fixed: Res<Time<Fixed>>,
+    arena: Res<Arena>,
+    physics: Res<PhysicsConfig>,
+    mut ev_messages: EventReader<ServerMessage>,
+    mut q: PredictedQuery,
+    others: ReplicatedQuery,
+) {
+    let Some(snapshot) = ev_messages
+        .read()
+        .filter_map(|ServerMessage(m)| match m {
+            NetMessage::Snapshot(snapshot) => Some(snapshot),
+            _ => None,
+        })
+        .max_by_key(|snapshot| snapshot.tick)
+    else {
+        return;
+    };
+    let dt = fixed.timestep().as_secs_f32();
+    let others: Vec<_> = others.iter().map(|(p, tf)| (p, tf.translation)).collect();
+    for (player, mut tf, mut previous, mut vel, mut predicted) in &mut q {
+        if snapshot.tick <= predicted.tick {
+            continue;
+        }
+        let Some(state) = snapshot.players.iter().find(|s| s.id == player.id) else {
+            continue;
+        };
+        predicted.tick = snapshot.tick;
+        predicted.pending.retain(|sent| sent.seq > snapshot.ack);
+
+        let mut pos = state.translation;
+        let mut v = state.velocity;
+        if !state.dead {
+            for sent in &predicted.pending {
+                step_player(&mut pos, &mut v, player, &sent.input, dt, &arena, &physics);
+                collide_with_players(
+                    (player, &mut pos, &mut v, sent.input.jump),
+                    &others,
+                    &arena,
+                    physics.jump_velocity,
+                );
+            }
+        }
+        let error = tf.translation - pos;
+        if error.length() > CORRECTION_SNAP_DISTANCE {
+            predicted.correction = Vec3::ZERO;
+            previous.0 = pos;
+        } else {
+            predicted.correction += error;
+            previous.0 -= error;
+        }
+        tf.translation = pos;
+        vel.0 = v;
+    }
+}
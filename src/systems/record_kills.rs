@@ -0,0 +1,11 @@
+use bevy::prelude::*;
+
+use crate::components::Scoreboard;
+use crate::events::PlayerKilled;
+
+pub fn record_kills(mut ev_killed: EventReader<PlayerKilled>, mut scoreboard: ResMut<Scoreboard>) {
+    for kill in ev_killed.read() {
+        scoreboard.players[kill.shooter].kills += 1;
+        scoreboard.players[kill.target].deaths += 1;
+    }
+}
@@ -0,0 +1,24 @@
+use bevy::prelude::*;
+
+use crate::components::{MatchRules, PositionHistory, SimTick};
+
+/// Appends where every player ended this tick and forgets positions older
+/// than the rewind window.
+pub fn record_position_history(
+    tick: Res<SimTick>,
+    rules: Res<MatchRules>,
+    fixed: Res<Time<Fixed>>,
+    mut q: Query<(&Transform, &mut PositionHistory)>,
+) {
+    let window = (rules.max_rewind / fixed.timestep().as_secs_f32()).ceil() as u64;
+    for (tf, mut history) in &mut q {
+        history.0.push_back((tick.0, tf.translation));
+        while history
+            .0
+            .front()
+            .is_some_and(|(t, _)| t.saturating_add(window) < tick.0)
+        {
+            history.0.pop_front();
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+
+use crate::components::{
+    Demo, DemoRecorder, MapDef, MapPath, MatchRng, MatchRules, PhysicsConfig, Player, PlayerCount,
+    SimTick, TickInput, ViewTick,
+};
+use crate::constants::{DEFAULT_MAP_PATH, DEMO_VERSION};
+use crate::helpers::{OthersFilter, match_contenders};
+
+/// Records the inputs the coming tick will simulate, and the view ticks its
+/// shots are checked against, in player id order, plus the map and physics
+/// whenever they change. A tick a rollback runs again replaces what was
+/// recorded for it. Runs last in `GameSet::Input`.
+#[allow(clippy::too_many_arguments)]
+pub fn record_tick_inputs(
+    fixed_time: Res<Time<Fixed>>,
+    tick: Res<SimTick>,
+    rng: Res<MatchRng>,
+    rules: Res<MatchRules>,
+    physics: Res<PhysicsConfig>,
+    map: Res<MapDef>,
+    map_path: Option<Res<MapPath>>,
+    player_count: Res<PlayerCount>,
+    mut recorder: ResMut<DemoRecorder>,
+    q: Query<(&Player, &TickInput, Option<&ViewTick>)>,
+    q_others: Query<&Player, OthersFilter>,
+) {
+    if recorder.finished {
+        return;
+    }
+    let demo = recorder.demo.get_or_insert_with(|| Demo {
+        version: DEMO_VERSION,
+        seed: rng.seed,
+        tick_rate: 1.0 / fixed_time.timestep().as_secs_f64(),
+        map: map_path.map_or_else(|| DEFAULT_MAP_PATH.into(), |m| m.0.clone()),
+        rules: rules.clone(),
+        maps: Vec::new(),
+        physics: Vec::new(),
+        players: 0,
+        ticks: Vec::new(),
+        view_ticks: Vec::new(),
+    });
+    demo.players = demo.players.max(match_contenders(player_count.0, q_others));
+    let mut inputs: Vec<_> = q.iter().collect();
+    inputs.sort_by_key(|(p, ..)| p.id);
+    record_change(&mut demo.maps, tick.0, &*map);
+    record_change(&mut demo.physics, tick.0, &*physics);
+    demo.ticks.truncate(tick.0 as usize);
+    demo.view_ticks.truncate(tick.0 as usize);
+    demo.ticks
+        .push(inputs.iter().map(|(_, input, _)| **input).collect());
+    demo.view_ticks
+        .push(inputs.iter().map(|(.., view)| view.map(|v| v.0)).collect());
+}
+
+/// Adds `value` from `tick` on unless it is already in force, forgetting
+/// changes recorded for ticks being run again.
+fn record_change<T: Clone + PartialEq>(changes: &mut Vec<(u64, T)>, tick: u64, value: &T) {
+    changes.retain(|(t, _)| *t < tick);
+    if changes.last().is_none_or(|(_, last)| last != value) {
+        changes.push((tick, value.clone()));
+    }
+}
@@ -0,0 +1,18 @@
+use bevy::prelude::*;
+
+use crate::components::{Dead, Player, Scoreboard};
+
+/// Runs on leaving `AppMode::MatchOver` to start a fresh match: every player
+/// respawns on the next tick, with full health and standing still.
+pub fn reset_match(
+    mut commands: Commands,
+    mut scoreboard: ResMut<Scoreboard>,
+    q: Query<Entity, With<Player>>,
+) {
+    *scoreboard = Scoreboard::default();
+    for entity in &q {
+        commands.entity(entity).insert(Dead {
+            respawn: Timer::default(),
+        });
+    }
+}
@@ -0,0 +1,34 @@
+use bevy::prelude::*;
+
+use crate::components::{Arena, Dead, PhysicsConfig, Player, TickInput, Velocity};
+use crate::helpers::{collide_players, confine_player};
+
+/// Pushes overlapping players apart, treating each as a vertical capsule from
+/// the feet sphere to the head sphere. Pairs are resolved in player id order
+/// so the outcome never depends on query iteration order.
+pub fn resolve_player_collisions(
+    arena: Res<Arena>,
+    physics: Res<PhysicsConfig>,
+    mut q: Query<(&Player, &mut Transform, &mut Velocity, &TickInput), Without<Dead>>,
+) {
+    let mut players: Vec<_> = q.iter_mut().collect();
+    players.sort_by_key(|(p, ..)| p.id);
+
+    for i in 0..players.len() {
+        for j in i + 1..players.len() {
+            let (lower, upper) = players.split_at_mut(j);
+            let (a, a_tf, a_vel, a_input) = &mut lower[i];
+            let (b, b_tf, b_vel, b_input) = &mut upper[0];
+            collide_players(
+                (a, &mut a_tf.translation, &mut a_vel.0, a_input.jump),
+                (b, &mut b_tf.translation, &mut b_vel.0, b_input.jump),
+                physics.jump_velocity,
+            );
+        }
+    }
+
+    // Pushes may not leave the arena or end inside an obstacle
+    for (p, tf, _, _) in &mut players {
+        confine_player(&arena, p, &mut tf.translation);
+    }
+}
@@ -0,0 +1,114 @@
+use bevy::prelude::*;
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+use crate::components::{
+    Arena, Dead, Health, MatchRng, MatchRules, Player, PreviousTranslation, SimTick, SpawnPoints,
+    TickInput, Velocity,
+};
+use crate::events::PlayerRespawned;
+use crate::helpers::{capsule_overlaps, choose_spawn_point, eye_position};
+
+/// Floor positions sampled when the map has no designated spawn points.
+const FLOOR_SAMPLES: usize = 16;
+
+type RespawnQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Player,
+        &'static mut Dead,
+        &'static mut Transform,
+        &'static mut PreviousTranslation,
+        &'static mut Velocity,
+        &'static mut Health,
+    ),
+>;
+
+#[allow(clippy::too_many_arguments)]
+pub fn respawn_players(
+    mut commands: Commands,
+    time: Res<Time>,
+    rules: Res<MatchRules>,
+    arena: Res<Arena>,
+    spawn_points: Res<SpawnPoints>,
+    tick: Res<SimTick>,
+    mut rng: ResMut<MatchRng>,
+    mut q: RespawnQuery,
+    q_living: Query<(&Player, &Transform, &TickInput), Without<Dead>>,
+    mut ev_respawned: EventWriter<PlayerRespawned>,
+) {
+    let living: Vec<(usize, Vec3, Vec3)> = q_living
+        .iter()
+        .map(|(p, tf, input)| (p.id, eye_position(tf.translation), input.forward()))
+        .collect();
+
+    // Respawn in id order so the RNG is drawn from the same way every run
+    let mut dead_players: Vec<_> = q.iter_mut().collect();
+    dead_players.sort_by_key(|(_, p, ..)| p.id);
+    for (entity, player, mut dead, mut tf, mut previous, mut vel, mut health) in dead_players {
+        if !dead.respawn.tick(time.delta()).finished() {
+            continue;
+        }
+        let points: Vec<Vec3> = if spawn_points.0.is_empty() {
+            floor_samples(&mut rng.rng, &arena, player.radius)
+        } else {
+            // Shuffled so equally safe points are not always taken in order
+            let mut points = spawn_points.0.clone();
+            points.shuffle(&mut rng.rng);
+            points
+        };
+        // The transform is the head centre, `height` above the feet
+        let lift = Vec3::Y * player.height;
+        let open = |points: &[Vec3]| -> Vec<Vec3> {
+            points
+                .iter()
+                .map(|c| *c + lift)
+                .filter(|c| !capsule_overlaps(&arena.obstacles, *c, player.radius, player.height))
+                .collect()
+        };
+        let mut candidates = open(&points);
+        // Every designated point is blocked: try the open floor instead
+        if candidates.is_empty() && !spawn_points.0.is_empty() {
+            candidates = open(&floor_samples(&mut rng.rng, &arena, player.radius));
+        }
+        // Nowhere is clear: spawn anyway and let collision push the player out
+        if candidates.is_empty() {
+            candidates = points.iter().map(|c| *c + lift).collect();
+        }
+        let enemies: Vec<(Vec3, Vec3)> = living
+            .iter()
+            .filter(|(id, _, _)| *id != player.id)
+            .map(|(_, eye, forward)| (*eye, *forward))
+            .collect();
+        let Some(spawn) = choose_spawn_point(&candidates, &enemies, &arena.obstacles) else {
+            continue;
+        };
+        tf.translation = spawn;
+        // Teleport rather than blend across the arena
+        previous.0 = spawn;
+        vel.0 = Vec3::ZERO;
+        health.0 = rules.max_health;
+        commands.entity(entity).remove::<Dead>();
+        ev_respawned.write(PlayerRespawned {
+            player: player.id,
+            location: tf.translation,
+            tick: tick.0,
+        });
+    }
+}
+
+/// Random points on the arena floor, far enough from the walls for `radius`.
+fn floor_samples(rng: &mut impl Rng, arena: &Arena, radius: f32) -> Vec<Vec3> {
+    let extent = arena.half_extent - radius;
+    (0..FLOOR_SAMPLES)
+        .map(|_| {
+            Vec3::new(
+                rng.gen_range(-extent..extent),
+                arena.floor(),
+                rng.gen_range(-extent..extent),
+            )
+        })
+        .collect()
+}
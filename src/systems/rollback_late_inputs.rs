@@ -0,0 +1,77 @@
+use bevy::app::FixedMain;
+use bevy::prelude::*;
+
+use crate::components::{
+    Dead, Health, MatchRng, Player, PositionHistory, RollbackSession, Scoreboard, SimTick,
+    TickInput, Velocity,
+};
+
+/// Takes in the peers' inputs before this frame's ticks. When one shows that
+/// an earlier tick ran with a wrong guess, restores the match as it was before
+/// that tick and runs every tick since again.
+pub fn rollback_late_inputs(world: &mut World) {
+    let now = world.resource::<SimTick>().0;
+    let real = world.resource::<Time<Real>>().elapsed_secs_f64();
+    let mut session = world.resource_mut::<RollbackSession>();
+    let Some(from) = session.receive(real).filter(|&tick| tick < now) else {
+        return;
+    };
+    let Some(state) = session.states.iter().find(|s| s.tick == from).cloned() else {
+        warn!("tick {from} is too old to roll back to; the peers have diverged");
+        return;
+    };
+    session.rollbacks += 1;
+    // The ticks that ended the match may play out differently now
+    session.ending = None;
+    let local = session.local;
+
+    // Ticks run again play our inputs as first played, over the live ones
+    // sampled this frame; keep those for the ticks still to come
+    let live = world
+        .query::<(Entity, &Player, &TickInput)>()
+        .iter(world)
+        .find(|(_, player, _)| player.id == local)
+        .map(|(entity, player, input)| (entity, player.yaw, player.pitch, *input));
+
+    for saved in state.players {
+        let mut entity = world.entity_mut(saved.entity);
+        if let Some(mut player) = entity.get_mut::<Player>() {
+            player.yaw = saved.yaw;
+            player.pitch = saved.pitch;
+        }
+        if let Some(mut tf) = entity.get_mut::<Transform>() {
+            *tf = saved.transform;
+        }
+        if let Some(mut vel) = entity.get_mut::<Velocity>() {
+            vel.0 = saved.velocity;
+        }
+        if let Some(mut health) = entity.get_mut::<Health>() {
+            health.0 = saved.health;
+        }
+        if let Some(mut history) = entity.get_mut::<PositionHistory>() {
+            history.0 = saved.history;
+        }
+        match saved.dead {
+            Some(respawn) => entity.insert(Dead { respawn }),
+            None => entity.remove::<Dead>(),
+        };
+    }
+    *world.resource_mut::<Scoreboard>() = state.scoreboard;
+    world.resource_mut::<MatchRng>().rng = state.rng;
+    world.resource_mut::<SimTick>().0 = from;
+
+    // Ticks see the fixed clock, as in the regular fixed loop
+    *world.resource_mut::<Time>() = world.resource::<Time<Fixed>>().as_generic();
+    for _ in from..now {
+        world.run_schedule(FixedMain);
+    }
+    if let Some((entity, yaw, pitch, input)) = live {
+        let mut entity = world.entity_mut(entity);
+        if let Some(mut player) = entity.get_mut::<Player>() {
+            player.yaw = yaw;
+            player.pitch = pitch;
+        }
+        entity.insert(input);
+    }
+    *world.resource_mut::<Time>() = world.resource::<Time<Virtual>>().as_generic();
+}
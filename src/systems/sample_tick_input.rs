@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+
+use crate::components::{Action, ActionState, Bot, Player, TickInput};
+
+/// Copies this frame's actions into each player's `TickInput`. Fire stays set
+/// until a fixed tick consumes it, so clicks between ticks are not lost, and
+/// the aim is held with it so the shot goes where the crosshair was. Bots
+/// write their own input.
+pub fn sample_tick_input(mut q: Query<(&Player, &ActionState, &mut TickInput), Without<Bot>>) {
+    for (player, actions, mut input) in &mut q {
+        input.movement = actions.move_axis();
+        input.jump = actions.pressed(Action::Jump);
+        if input.fire {
+            continue;
+        }
+        input.fire = actions.just_pressed(Action::Fire);
+        input.yaw = player.yaw;
+        input.pitch = player.pitch;
+    }
+}
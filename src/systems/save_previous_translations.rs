@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+
+use crate::components::PreviousTranslation;
+
+/// Runs first in every fixed tick so rendering can blend from the old state.
+pub fn save_previous_translations(mut q: Query<(&Transform, &mut PreviousTranslation)>) {
+    for (tf, mut previous) in &mut q {
+        previous.0 = tf.translation;
+    }
+}
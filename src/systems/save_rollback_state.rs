@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+
+use crate::components::{
+    Dead, Health, MatchRng, Player, PositionHistory, RollbackSession, SavedPlayer, SavedState,
+    Scoreboard, SimTick, Velocity,
+};
+use crate::constants::ROLLBACK_WINDOW;
+
+type SavedQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Player,
+        &'static Transform,
+        &'static Velocity,
+        &'static Health,
+        Option<&'static Dead>,
+        &'static PositionHistory,
+    ),
+>;
+
+/// Saves the match before the tick runs, replacing what a rollback is about
+/// to simulate again, and forgets states too old to roll back to.
+pub fn save_rollback_state(
+    tick: Res<SimTick>,
+    scoreboard: Res<Scoreboard>,
+    rng: Res<MatchRng>,
+    mut session: ResMut<RollbackSession>,
+    q: SavedQuery,
+) {
+    let players = q
+        .iter()
+        .map(
+            |(entity, player, tf, vel, health, dead, history)| SavedPlayer {
+                entity,
+                yaw: player.yaw,
+                pitch: player.pitch,
+                transform: *tf,
+                velocity: vel.0,
+                health: health.0,
+                dead: dead.map(|d| d.respawn.clone()),
+                history: history.0.clone(),
+            },
+        )
+        .collect();
+    let states = &mut session.states;
+    while states.back().is_some_and(|s| s.tick >= tick.0) {
+        states.pop_back();
+    }
+    states.push_back(SavedState {
+        tick: tick.0,
+        players,
+        scoreboard: scoreboard.clone(),
+        rng: rng.rng.clone(),
+    });
+    while states.len() as u64 > ROLLBACK_WINDOW + 1 {
+        states.pop_front();
+    }
+}
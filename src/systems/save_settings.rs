@@ -0,0 +1,14 @@
+use bevy::prelude::*;
+
+use crate::components::SettingsRes;
+use crate::constants::SETTINGS_PATH;
+use crate::helpers::save_config;
+
+pub fn save_settings(settings: Res<SettingsRes>) {
+    if !settings.is_changed() || settings.is_added() {
+        return;
+    }
+    if let Err(e) = save_config(SETTINGS_PATH, &*settings) {
+        warn!("failed to save settings to {SETTINGS_PATH}: {e}");
+    }
+}
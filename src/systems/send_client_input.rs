@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+
+use crate::components::{NetClient, NetMessage, Predicted, SentInput, TickInput};
+use crate::constants::{NET_INPUT_REDUNDANCY, PREDICTION_MAX_PENDING};
+use crate::helpers::send_message;
+
+/// Sends our player's input for this tick to the server along with the
+/// recent ones it has not acknowledged, and keeps it for reconciliation. The
+/// server takes the shot, so a latched fire is consumed here.
+pub fn send_client_input(
+    mut client: ResMut<NetClient>,
+    mut q: Query<(&mut TickInput, &mut Predicted)>,
+) {
+    for (mut input, mut predicted) in &mut q {
+        client.seq += 1;
+        predicted.pending.push_back(SentInput {
+            seq: client.seq,
+            input: *input,
+            view_tick: client.tick,
+        });
+        if predicted.pending.len() > PREDICTION_MAX_PENDING {
+            predicted.pending.pop_front();
+        }
+        let resend = predicted.pending.len().min(NET_INPUT_REDUNDANCY);
+        let message = NetMessage::Input(
+            predicted
+                .pending
+                .iter()
+                .skip(predicted.pending.len() - resend)
+                .copied()
+                .collect(),
+        );
+        send_message(&client.socket, None, &message);
+        input.fire = false;
+    }
+}
@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+use crate::components::RollbackSession;
+
+pub fn send_peer_inputs(mut session: ResMut<RollbackSession>) {
+    session.send();
+}
@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+
+use crate::components::{
+    AppMode, Dead, Health, NetMessage, NetServer, Player, PlayerState, RemotePlayer, Scoreboard,
+    SimTick, Snapshot, Velocity,
+};
+use crate::helpers::send_message;
+
+/// Sends every remote client the state of the match after this tick.
+pub fn send_snapshots(
+    server: Res<NetServer>,
+    tick: Res<SimTick>,
+    mode: Res<State<AppMode>>,
+    scoreboard: Res<Scoreboard>,
+    q_players: Query<(&Player, &Transform, &Velocity, &Health, Has<Dead>)>,
+    q_remotes: Query<&RemotePlayer>,
+) {
+    if q_remotes.is_empty() {
+        return;
+    }
+    let mut players: Vec<PlayerState> = q_players
+        .iter()
+        .map(|(player, tf, vel, health, dead)| PlayerState {
+            id: player.id,
+            translation: tf.translation,
+            velocity: vel.0,
+            yaw: player.yaw,
+            pitch: player.pitch,
+            health: health.0,
+            dead,
+        })
+        .collect();
+    players.sort_by_key(|p| p.id);
+    let mut snapshot = Snapshot {
+        tick: tick.0,
+        ack: 0,
+        match_over: *mode.get() == AppMode::MatchOver,
+        players,
+        scoreboard: scoreboard.clone(),
+    };
+    for remote in &q_remotes {
+        snapshot.ack = remote.ack;
+        send_message(
+            &server.socket,
+            Some(remote.addr),
+            &NetMessage::Snapshot(snapshot.clone()),
+        );
+    }
+}
@@ -1,7 +1,10 @@
 use bevy::{
     core_pipeline::prelude::Camera3d,
     prelude::*,
-    render::camera::{PerspectiveProjection, Projection},
+    render::{
+        camera::{ClearColorConfig, PerspectiveProjection, Projection},
+        view::RenderLayers,
+    },
 };
 
 use crate::components::PlayerCamera;
@@ -23,4 +26,16 @@ pub fn setup_cameras(mut commands: Commands, player_count: Res<PlayerCount>) {
             PlayerCamera { player_id: i },
         ));
     }
+
+    // Full-window overlay camera for menus; kept off the gizmo render layer
+    commands.spawn((
+        Camera2d,
+        Camera {
+            order: 1,
+            clear_color: ClearColorConfig::None,
+            ..default()
+        },
+        RenderLayers::layer(1),
+        IsDefaultUiCamera,
+    ));
 }
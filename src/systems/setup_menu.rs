@@ -0,0 +1,109 @@
+use bevy::prelude::*;
+use bevy::ui::RelativeCursorPosition;
+
+use crate::components::{
+    AppMode, MenuEntry, MenuItem, MenuLabel, MenuPage, SliderFill, SliderTrack,
+};
+use crate::helpers::slider_range;
+
+pub fn setup_menu(mut commands: Commands) {
+    spawn_page(
+        &mut commands,
+        AppMode::Menu,
+        "Paused",
+        &[
+            MenuItem::Resume,
+            MenuItem::Settings,
+            MenuItem::PlayerCount,
+            MenuItem::Quit,
+        ],
+    );
+    spawn_page(
+        &mut commands,
+        AppMode::Settings,
+        "Settings",
+        &[
+            MenuItem::Player,
+            MenuItem::Sensitivity,
+            MenuItem::Crosshair,
+            MenuItem::GamepadSensitivity,
+            MenuItem::Bot,
+            MenuItem::Bindings,
+            MenuItem::Back,
+        ],
+    );
+}
+
+fn spawn_page(commands: &mut Commands, page: AppMode, title: &str, items: &[MenuItem]) {
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(12.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            Visibility::Hidden,
+            MenuPage(page),
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Text::new(title),
+                TextFont {
+                    font_size: 48.0,
+                    ..default()
+                },
+            ));
+            for (index, &item) in items.iter().enumerate() {
+                root.spawn((
+                    Button,
+                    Node {
+                        width: Val::Px(480.0),
+                        padding: UiRect::axes(Val::Px(16.0), Val::Px(8.0)),
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(6.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.15, 0.15, 0.15)),
+                    MenuEntry { page, index, item },
+                ))
+                .with_children(|row| {
+                    row.spawn((
+                        Text::default(),
+                        TextFont {
+                            font_size: 24.0,
+                            ..default()
+                        },
+                        MenuLabel(item),
+                    ));
+                    if slider_range(item).is_some() {
+                        row.spawn((
+                            Node {
+                                width: Val::Percent(100.0),
+                                height: Val::Px(12.0),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+                            RelativeCursorPosition::default(),
+                            SliderTrack(item),
+                        ))
+                        .with_children(|track| {
+                            track.spawn((
+                                Node {
+                                    width: Val::Percent(0.0),
+                                    height: Val::Percent(100.0),
+                                    ..default()
+                                },
+                                BackgroundColor(Color::srgb(0.9, 0.9, 0.9)),
+                                SliderFill(item),
+                            ));
+                        });
+                    }
+                });
+            }
+        });
+}
@@ -1,9 +1,12 @@
 use bevy::prelude::*;
 
-use crate::components::{Player, Velocity};
+use crate::components::{
+    ActionState, Health, InputAssignment, MatchRules, Player, PositionHistory, PreviousTranslation,
+    TickInput, Velocity,
+};
 use crate::constants::MAX_PLAYER_COUNT;
 
-pub fn setup_players(mut commands: Commands) {
+pub fn setup_players(mut commands: Commands, rules: Res<MatchRules>) {
     // Optional light (not required for gizmos, keep for completeness)
     commands.spawn((
         DirectionalLight::default(),
@@ -49,7 +52,18 @@ pub fn setup_players(mut commands: Commands) {
                 color,
             },
             Velocity(Vec3::ZERO),
+            Health(rules.max_health),
+            // Player 0 starts with the mouse and the first keyboard layout
+            InputAssignment {
+                mouse: i == 0,
+                keyboard: (i == 0).then_some(0),
+                gamepad: None,
+            },
+            ActionState::default(),
+            TickInput::default(),
             Transform::from_translation(pos),
+            PreviousTranslation(pos),
+            PositionHistory::default(),
             GlobalTransform::default(),
         ));
     }
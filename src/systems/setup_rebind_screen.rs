@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+
+use crate::components::{AppMode, MenuPage, RebindText};
+
+pub fn setup_rebind_screen(mut commands: Commands) {
+    commands.spawn((
+        Text::default(),
+        TextFont {
+            font_size: 20.0,
+            ..default()
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(24.0),
+            left: Val::Px(24.0),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.75)),
+        Visibility::Hidden,
+        MenuPage(AppMode::Bindings),
+        RebindText,
+    ));
+}
@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+
+use crate::components::{PlayerCamera, ScoreboardText};
+
+/// Spawns one scoreboard overlay per player camera, rendered in its viewport.
+pub fn setup_scoreboard(mut commands: Commands, cams: Query<(Entity, &PlayerCamera)>) {
+    for (cam, cam_tag) in &cams {
+        commands.spawn((
+            Text::default(),
+            TextFont {
+                font_size: 20.0,
+                ..default()
+            },
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(10.0),
+                left: Val::Percent(10.0),
+                padding: UiRect::all(Val::Px(12.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+            Visibility::Hidden,
+            UiTargetCamera(cam),
+            ScoreboardText {
+                player_id: cam_tag.player_id,
+            },
+        ));
+    }
+}
@@ -0,0 +1,35 @@
+use bevy::{
+    core_pipeline::prelude::Camera3d,
+    prelude::*,
+    render::camera::{PerspectiveProjection, Projection},
+};
+
+use crate::components::{ActionState, InputAssignment, Player, Spectator};
+
+/// Spawns the spectator camera. It takes the mouse and the first keyboard
+/// layout; players join on the other devices.
+pub fn setup_spectator(
+    mut commands: Commands,
+    mut q_players: Query<&mut InputAssignment, With<Player>>,
+) {
+    for mut assignment in &mut q_players {
+        assignment.mouse = false;
+        if assignment.keyboard == Some(0) {
+            assignment.keyboard = None;
+        }
+    }
+    commands.spawn((
+        Camera::default(),
+        Camera3d::default(),
+        Projection::Perspective(PerspectiveProjection::default()),
+        Transform::from_xyz(0.0, 0.0, 12.0),
+        GlobalTransform::default(),
+        Spectator::default(),
+        InputAssignment {
+            mouse: true,
+            keyboard: Some(0),
+            gamepad: None,
+        },
+        ActionState::default(),
+    ));
+}
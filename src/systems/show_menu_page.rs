@@ -0,0 +1,22 @@
+use bevy::prelude::*;
+
+use crate::components::{AppMode, MenuFocus, MenuPage, RebindState};
+
+/// Runs on entering any mode: shows that mode's page, hides the others and
+/// resets per-page state.
+pub fn show_menu_page(
+    mode: Res<State<AppMode>>,
+    mut focus: ResMut<MenuFocus>,
+    mut rebind: ResMut<RebindState>,
+    mut q_pages: Query<(&MenuPage, &mut Visibility)>,
+) {
+    for (page, mut visibility) in &mut q_pages {
+        *visibility = if page.0 == *mode.get() {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+    focus.0 = 0;
+    rebind.capturing = false;
+}
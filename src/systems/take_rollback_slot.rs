@@ -0,0 +1,34 @@
+use bevy::prelude::*;
+
+use crate::components::{InputAssignment, PeerPlayer, Player, PlayerCamera, RollbackSession};
+
+/// Moves the local devices and first camera from player 1 to our slot in a
+/// rollback match, and marks the slots the other peers play.
+pub fn take_rollback_slot(
+    mut commands: Commands,
+    session: Res<RollbackSession>,
+    mut q_players: Query<(Entity, &Player, &mut InputAssignment)>,
+    mut q_cameras: Query<&mut PlayerCamera>,
+) {
+    let id = session.local;
+    let mut local = InputAssignment::default();
+    for (_, player, mut assignment) in &mut q_players {
+        if player.id == 0 {
+            local = std::mem::take(&mut *assignment);
+        }
+    }
+    for (entity, player, mut assignment) in &mut q_players {
+        if player.id == id {
+            *assignment = std::mem::take(&mut local);
+        } else if session.players.contains(&player.id) {
+            commands.entity(entity).insert(PeerPlayer);
+        }
+    }
+    for mut camera in &mut q_cameras {
+        if camera.player_id == 0 {
+            camera.player_id = id;
+        } else if camera.player_id == id {
+            camera.player_id = 0;
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use bevy::prelude::*;
+
+use crate::components::{
+    Action, ActionBindings, ActionState, Binding, BindingTable, InputAssignment, MouseDelta,
+    Player, RebindState, SettingsRes,
+};
+use crate::helpers::apply_dead_zone;
+
+/// Translates raw device input into `ActionState`s: one per player (or
+/// spectator) from the devices they claimed, plus the global resource read
+/// from every device.
+#[allow(clippy::too_many_arguments)]
+pub fn update_action_state(
+    bindings: Res<ActionBindings>,
+    settings: Res<SettingsRes>,
+    rebind: Res<RebindState>,
+    time: Res<Time>,
+    kb: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mut mouse_delta: ResMut<MouseDelta>,
+    gamepads: Query<&Gamepad>,
+    mut global: ResMut<ActionState>,
+    mut q_players: Query<(Option<&Player>, &InputAssignment, &mut ActionState)>,
+) {
+    let mouse_motion = Vec2::new(-mouse_delta.dx, -mouse_delta.dy);
+    mouse_delta.dx = 0.0;
+    mouse_delta.dy = 0.0;
+
+    global.clear();
+    for (_, _, mut actions) in &mut q_players {
+        actions.clear();
+    }
+    // The rebinding screen swallows input until the new binding is captured
+    if rebind.capturing {
+        return;
+    }
+
+    let all_pads: Vec<&Gamepad> = gamepads.iter().collect();
+    read_table(
+        &bindings.global,
+        &Action::GLOBAL,
+        &kb,
+        &mouse_buttons,
+        &all_pads,
+        &mut global,
+    );
+
+    for (player, assignment, mut actions) in &mut q_players {
+        // Spectators share the first slot's sensitivity
+        let settings = settings.player(player.map_or(0, |p| p.id));
+        if let Some(table) = assignment.keyboard.and_then(|k| bindings.keyboards.get(k)) {
+            read_table(
+                table,
+                &Action::PLAYER,
+                &kb,
+                &mouse_buttons,
+                &[],
+                &mut actions,
+            );
+        }
+        if assignment.mouse {
+            read_table(
+                &bindings.mouse,
+                &Action::PLAYER,
+                &kb,
+                &mouse_buttons,
+                &[],
+                &mut actions,
+            );
+            actions.look += mouse_motion * settings.sensitivity;
+        }
+        if let Some(pad) = assignment.gamepad.and_then(|e| gamepads.get(e).ok()) {
+            let pads = [pad];
+            read_table(
+                &bindings.gamepad,
+                &Action::PLAYER,
+                &kb,
+                &mouse_buttons,
+                &pads,
+                &mut actions,
+            );
+            actions.movement += apply_dead_zone(pad.left_stick(), settings.gamepad_dead_zone);
+            // Stick y is up-positive unlike mouse y
+            let look = apply_dead_zone(pad.right_stick(), settings.gamepad_dead_zone);
+            actions.look +=
+                Vec2::new(-look.x, look.y) * settings.gamepad_sensitivity * time.delta_secs();
+        }
+    }
+}
+
+fn read_table(
+    table: &BindingTable,
+    actions: &[Action],
+    kb: &ButtonInput<KeyCode>,
+    mouse_buttons: &ButtonInput<MouseButton>,
+    pads: &[&Gamepad],
+    state: &mut ActionState,
+) {
+    for &action in actions {
+        for binding in table.bindings(action) {
+            let (pressed, just_pressed) = match binding {
+                Binding::Key(k) => (kb.pressed(k), kb.just_pressed(k)),
+                Binding::Mouse(b) => (mouse_buttons.pressed(b), mouse_buttons.just_pressed(b)),
+                Binding::Gamepad(b) => (
+                    pads.iter().any(|p| p.pressed(b)),
+                    pads.iter().any(|p| p.just_pressed(b)),
+                ),
+            };
+            state.set(action, pressed, just_pressed);
+        }
+    }
+}
@@ -1,17 +1,30 @@
 use bevy::prelude::*;
 
-use crate::components::{Player, PlayerCamera};
+use crate::components::{Player, PlayerCamera, Predicted, PreviousTranslation};
+use crate::helpers::{eye_position, look_direction};
 
+type CameraFilter = (With<Camera>, Without<Player>);
+
+/// Puts each camera at its player's eye, blended between ticks and offset by
+/// any prediction correction still fading out on a client.
 pub fn update_camera_transforms(
-    q_players: Query<(&Transform, &Player)>,
-    mut q_cams: Query<(&mut Transform, &PlayerCamera), (With<Camera>, Without<Player>)>,
+    fixed_time: Res<Time<Fixed>>,
+    q_players: Query<(
+        &Transform,
+        &PreviousTranslation,
+        &Player,
+        Option<&Predicted>,
+    )>,
+    mut q_cams: Query<(&mut Transform, &PlayerCamera), CameraFilter>,
 ) {
+    let alpha = fixed_time.overstep_fraction();
     for (mut cam_tf, cam) in &mut q_cams {
-        if let Some((player_tf, player)) = q_players.iter().find(|(_, p)| p.id == cam.player_id) {
-            let cam_pos = player_tf.translation + Vec3::Y * 0.5;
-            let (sin_yaw, cos_yaw) = player.yaw.sin_cos();
-            let (sin_pitch, cos_pitch) = player.pitch.sin_cos();
-            let dir = Vec3::new(-sin_yaw * cos_pitch, sin_pitch, -cos_yaw * cos_pitch);
+        if let Some((player_tf, previous, player, predicted)) =
+            q_players.iter().find(|(_, _, p, _)| p.id == cam.player_id)
+        {
+            let correction = predicted.map_or(Vec3::ZERO, |p| p.correction);
+            let cam_pos = eye_position(previous.lerp(player_tf.translation, alpha) + correction);
+            let dir = look_direction(player.yaw, player.pitch);
             *cam_tf = Transform::from_translation(cam_pos).looking_at(cam_pos + dir, Vec3::Y);
         }
     }
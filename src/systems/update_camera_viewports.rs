@@ -1,30 +1,52 @@
 use bevy::prelude::*;
 
-use crate::components::PlayerCount;
+use crate::components::{PlayerCamera, PlayerCount, Spectator};
 
+type ViewQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut Camera,
+        Option<&'static PlayerCamera>,
+        Has<Spectator>,
+    ),
+>;
+
+/// Splits the window between the joined players' cameras, followed by any
+/// spectator cameras.
 pub fn update_camera_viewports(
     windows: Query<&Window>,
-    mut q_cams: Query<(&mut Camera, &crate::components::PlayerCamera)>,
+    mut q_cams: ViewQuery,
     player_count: Res<PlayerCount>,
 ) {
-    let window = windows.single().expect("primary window");
+    // Nothing to lay out without a window, e.g. while the window is closing
+    let Ok(window) = windows.single() else {
+        return;
+    };
     let (w, h) = (
         window.resolution.physical_width(),
         window.resolution.physical_height(),
     );
-    let (part_hor, part_ver): (usize, usize) = if player_count.0 > 2 {
-        (2, 2)
-    } else if player_count.0 > 1 {
-        (2, 1)
-    } else {
-        (1, 1)
-    };
+    let spectators = q_cams.iter().filter(|(_, _, s)| *s).count();
+    let views = player_count.0 + spectators;
+    // 1x1, 2x1, 2x2, then 3x2 once a spectator joins four players
+    let part_hor = (views as f32).sqrt().ceil().max(1.0) as usize;
+    let part_ver = views.div_ceil(part_hor).max(1);
     let size_w: u32 = w / part_hor as u32;
     let size_h: u32 = h / part_ver as u32;
 
-    for (mut cam, cam_tag) in &mut q_cams {
-        let i = cam_tag.player_id;
-        let active = i < player_count.0;
+    let mut next_spectator = player_count.0;
+    for (mut cam, player_cam, spectator) in &mut q_cams {
+        let i = match (player_cam, spectator) {
+            (Some(cam_tag), _) => cam_tag.player_id,
+            (None, true) => {
+                next_spectator += 1;
+                next_spectator - 1
+            }
+            // The UI overlay camera
+            (None, false) => continue,
+        };
+        let active = spectator || i < player_count.0;
         cam.is_active = active;
         if !active {
             cam.viewport = None;
@@ -1,11 +1,13 @@
 use bevy::prelude::*;
 use bevy::window::{CursorGrabMode, CursorOptions};
 
-use crate::components::{AppMode, GameMode};
+use crate::components::AppMode;
 
-pub fn update_cursor_visibility(mode: Res<GameMode>, mut q: Query<&mut Window>) {
+/// Runs on entering and leaving `AppMode::Playing`; `State` already holds the
+/// new mode during `OnExit`.
+pub fn update_cursor_visibility(mode: Res<State<AppMode>>, mut q: Query<&mut Window>) {
     if let Ok(mut window) = q.single_mut() {
-        match mode.0 {
+        match mode.get() {
             AppMode::Playing => {
                 window.cursor_options = CursorOptions {
                     visible: false,
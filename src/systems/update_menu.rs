@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+
+use crate::components::{
+    AppMode, MenuEntry, MenuFocus, MenuItem, MenuLabel, PlayerCount, SettingsRes,
+    SettingsSelection, SliderFill,
+};
+use crate::helpers::slider_fraction;
+
+/// Refreshes labels, sliders and focus highlight of the visible menu page.
+#[allow(clippy::too_many_arguments)]
+pub fn update_menu(
+    mode: Res<State<AppMode>>,
+    focus: Res<MenuFocus>,
+    settings: Res<SettingsRes>,
+    selection: Res<SettingsSelection>,
+    player_count: Res<PlayerCount>,
+    mut q_entries: Query<(&MenuEntry, &mut BackgroundColor)>,
+    mut q_labels: Query<(&MenuLabel, &mut Text)>,
+    mut q_fills: Query<(&SliderFill, &mut Node)>,
+) {
+    for (entry, mut bg) in &mut q_entries {
+        bg.0 = if entry.page == *mode.get() && entry.index == focus.0 {
+            Color::srgb(0.35, 0.35, 0.45)
+        } else {
+            Color::srgb(0.15, 0.15, 0.15)
+        };
+    }
+
+    let player = settings.player(selection.0);
+    for (label, mut text) in &mut q_labels {
+        text.0 = match label.0 {
+            MenuItem::Resume => "Resume".into(),
+            MenuItem::Settings => "Settings".into(),
+            MenuItem::PlayerCount => format!("Players: < {} >", player_count.0),
+            MenuItem::Quit => "Quit".into(),
+            MenuItem::Player => format!("Editing player: < {} >", selection.0 + 1),
+            MenuItem::Sensitivity => format!("Mouse sensitivity: {:.4}", player.sensitivity),
+            MenuItem::Crosshair => format!("Crosshair size: {:.2}", player.crosshair_half),
+            MenuItem::GamepadSensitivity => {
+                format!("Gamepad look speed: {:.1}", player.gamepad_sensitivity)
+            }
+            MenuItem::Bot => match player.bot {
+                Some(difficulty) => format!("Bot when not joined: < {difficulty:?} >"),
+                None => "Bot when not joined: < Off >".into(),
+            },
+            MenuItem::Bindings => "Key bindings".into(),
+            MenuItem::Back => "Back".into(),
+        };
+    }
+    for (fill, mut node) in &mut q_fills {
+        node.width = Val::Percent(100.0 * slider_fraction(player, fill.0));
+    }
+}
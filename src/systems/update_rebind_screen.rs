@@ -0,0 +1,52 @@
+use std::fmt::Write;
+
+use bevy::prelude::*;
+
+use crate::components::{ActionBindings, Binding, RebindState, RebindText};
+
+pub fn update_rebind_screen(
+    bindings: Res<ActionBindings>,
+    rebind: Res<RebindState>,
+    mut q: Query<&mut Text, With<RebindText>>,
+) {
+    let Ok(mut text) = q.single_mut() else {
+        return;
+    };
+
+    let mut out = String::new();
+    let _ = writeln!(out, "Key bindings\n");
+    let tables = RebindState::tables(&bindings);
+    let _ = writeln!(
+        out,
+        "< {} >  (left/right to switch)\n",
+        tables[rebind.table]
+    );
+    let table = rebind.table(&bindings);
+    for (i, action) in rebind.actions(&bindings).iter().enumerate() {
+        let bound: Vec<String> = table.bindings(*action).map(binding_label).collect();
+        let marker = match (i == rebind.action, rebind.capturing) {
+            (true, true) => "?",
+            (true, false) => ">",
+            _ => " ",
+        };
+        let _ = writeln!(out, "{marker} {action:?}: {}", bound.join(", "));
+    }
+    let _ = write!(
+        out,
+        "\n{}",
+        if rebind.capturing {
+            "press the new key or button (Esc cancels)"
+        } else {
+            "up/down select, enter to rebind, Esc back"
+        }
+    );
+    text.0 = out;
+}
+
+fn binding_label(binding: Binding) -> String {
+    match binding {
+        Binding::Key(k) => format!("{k:?}"),
+        Binding::Mouse(b) => format!("Mouse {b:?}"),
+        Binding::Gamepad(b) => format!("Pad {b:?}"),
+    }
+}
@@ -0,0 +1,62 @@
+use std::fmt::Write;
+
+use bevy::prelude::*;
+
+use crate::components::{
+    Action, ActionState, AppMode, MatchRules, Player, PlayerCount, Scoreboard, ScoreboardText,
+};
+use crate::helpers::{OthersFilter, match_contenders};
+
+/// Shows the overlay while its player holds `Scoreboard`, and everywhere once
+/// the match is over.
+#[allow(clippy::too_many_arguments)]
+pub fn update_scoreboard(
+    mode: Res<State<AppMode>>,
+    fixed: Res<Time<Fixed>>,
+    rules: Res<MatchRules>,
+    scoreboard: Res<Scoreboard>,
+    player_count: Res<PlayerCount>,
+    q_players: Query<(&Player, &ActionState)>,
+    q_others: Query<&Player, OthersFilter>,
+    mut q_text: Query<(&ScoreboardText, &mut Text, &mut Visibility)>,
+) {
+    let contenders = match_contenders(player_count.0, q_others);
+    let match_over = *mode.get() == AppMode::MatchOver;
+    let mut out = String::new();
+    if match_over {
+        match scoreboard.winner(contenders) {
+            Some(id) => {
+                let _ = writeln!(out, "Match over - player {} wins", id + 1);
+            }
+            None => {
+                let _ = writeln!(out, "Match over - draw");
+            }
+        }
+    } else if let Some(limit) = rules.time_limit {
+        let left = (limit - scoreboard.elapsed(fixed.timestep())).max(0.0) as u32;
+        let _ = writeln!(out, "Time left {}:{:02}", left / 60, left % 60);
+    }
+    let _ = writeln!(out, "\nplayer   kills   deaths");
+    for id in scoreboard.ranking(contenders) {
+        let s = &scoreboard.players[id];
+        let _ = writeln!(out, "P{:<7} {:>5} {:>8}", id + 1, s.kills, s.deaths);
+    }
+    if match_over {
+        let _ = write!(out, "\nEsc starts a new match");
+    }
+
+    for (overlay, mut text, mut visibility) in &mut q_text {
+        let held = q_players
+            .iter()
+            .any(|(p, a)| p.id == overlay.player_id && a.pressed(Action::Scoreboard));
+        let shown = match_over || (*mode.get() == AppMode::Playing && held);
+        *visibility = if shown {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+        if shown {
+            text.0.clone_from(&out);
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use bevy::prelude::*;
+
+use crate::components::{
+    Action, ActionState, Arena, Player, PlayerCount, PreviousTranslation, Spectator, SpectatorMode,
+};
+use crate::helpers::{
+    OthersFilter, eye_position, look_angles, look_direction, match_contenders,
+    ray_obstacle_distance,
+};
+
+const FLY_SPEED: f32 = 12.0; // world units per second
+const THIRD_PERSON_DISTANCE: f32 = 4.0;
+/// Gap kept between a third-person camera and the wall behind it.
+const WALL_MARGIN: f32 = 0.2;
+
+type SpectatorQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut Transform,
+        &'static mut Spectator,
+        &'static ActionState,
+    ),
+    Without<Player>,
+>;
+
+/// `FreeFly`, then every contender in id order, then `FreeFly` again.
+fn next_mode(mode: SpectatorMode, ids: &[usize]) -> SpectatorMode {
+    let next = match mode {
+        SpectatorMode::FreeFly => ids.first(),
+        SpectatorMode::Follow(current) => ids.iter().find(|&&id| id > current),
+    };
+    next.map_or(SpectatorMode::FreeFly, |&id| SpectatorMode::Follow(id))
+}
+
+/// Flies or follows each spectator camera; runs after the player cameras so
+/// followed views match them. Idle slots nobody joined are not followed.
+pub fn update_spectators(
+    time: Res<Time>,
+    fixed_time: Res<Time<Fixed>>,
+    arena: Res<Arena>,
+    player_count: Res<PlayerCount>,
+    q_players: Query<(&Transform, &PreviousTranslation, &Player)>,
+    q_others: Query<&Player, OthersFilter>,
+    mut q_spectators: SpectatorQuery,
+) {
+    let alpha = fixed_time.overstep_fraction();
+    let contenders = match_contenders(player_count.0, q_others);
+    let mut targets: Vec<(usize, Vec3, Vec3)> = q_players
+        .iter()
+        .filter(|(.., p)| p.id < contenders)
+        .map(|(tf, previous, p)| {
+            let eye = eye_position(previous.lerp(tf.translation, alpha));
+            (p.id, eye, look_direction(p.yaw, p.pitch))
+        })
+        .collect();
+    targets.sort_by_key(|(id, ..)| *id);
+    let ids: Vec<usize> = targets.iter().map(|(id, ..)| *id).collect();
+
+    for (mut tf, mut spectator, actions) in &mut q_spectators {
+        if actions.just_pressed(Action::Fire) {
+            spectator.mode = next_mode(spectator.mode, &ids);
+            if spectator.mode == SpectatorMode::FreeFly {
+                // Take off from the view being left
+                (spectator.yaw, spectator.pitch) = look_angles(*tf.forward());
+            }
+        }
+        let target = match spectator.mode {
+            SpectatorMode::Follow(id) => targets.iter().find(|(t, ..)| *t == id),
+            SpectatorMode::FreeFly => None,
+        };
+
+        let Some(&(_, eye, dir)) = target else {
+            spectator.mode = SpectatorMode::FreeFly;
+            spectator.yaw += actions.look.x;
+            spectator.pitch = (spectator.pitch + actions.look.y).clamp(-1.6, 1.6);
+            let dir = look_direction(spectator.yaw, spectator.pitch);
+            let right = dir.cross(Vec3::Y).normalize_or_zero();
+            let axis = actions.move_axis();
+            let rise = actions.pressed(Action::Jump) as u8 as f32;
+            let velocity = (dir * axis.y + right * axis.x + Vec3::Y * rise) * FLY_SPEED;
+            let bound = Vec3::splat(arena.half_extent);
+            let pos = (tf.translation + velocity * time.delta_secs()).clamp(-bound, bound);
+            *tf = Transform::from_translation(pos).looking_to(dir, Vec3::Y);
+            continue;
+        };
+
+        if actions.just_pressed(Action::Jump) {
+            spectator.third_person = !spectator.third_person;
+        }
+        *tf = if spectator.third_person {
+            let distance = ray_obstacle_distance(&arena.obstacles, eye, -dir)
+                .map_or(THIRD_PERSON_DISTANCE, |wall| {
+                    (wall - WALL_MARGIN).clamp(0.0, THIRD_PERSON_DISTANCE)
+                });
+            let bound = Vec3::splat(arena.half_extent - WALL_MARGIN);
+            let pos = (eye - dir * distance).clamp(-bound, bound);
+            Transform::from_translation(pos).looking_to(dir, Vec3::Y)
+        } else {
+            Transform::from_translation(eye).looking_to(dir, Vec3::Y)
+        };
+    }
+}
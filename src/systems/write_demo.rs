@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+
+use crate::components::DemoRecorder;
+use crate::helpers::save_demo;
+
+/// Saves the recording once, when the match ends or the app exits first.
+pub fn write_demo(mut recorder: ResMut<DemoRecorder>) {
+    if recorder.finished {
+        return;
+    }
+    recorder.finished = true;
+    let Some(demo) = &recorder.demo else {
+        return;
+    };
+    match save_demo(&recorder.path, demo) {
+        Ok(()) => info!(
+            "recorded {} ticks to {}",
+            demo.ticks.len(),
+            recorder.path.display()
+        ),
+        Err(e) => warn!("failed to write demo {}: {e}", recorder.path.display()),
+    }
+}
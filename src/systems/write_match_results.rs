@@ -0,0 +1,48 @@
+use bevy::prelude::*;
+
+use crate::components::{MatchResults, Player, PlayerCount, PlayerResult, ResultsPath, Scoreboard};
+use crate::helpers::{OthersFilter, match_contenders};
+
+/// Runs on entering `AppMode::MatchOver`: prints the standings and writes them
+/// as JSON when a results path was given.
+pub fn write_match_results(
+    fixed: Res<Time<Fixed>>,
+    scoreboard: Res<Scoreboard>,
+    player_count: Res<PlayerCount>,
+    results_path: Res<ResultsPath>,
+    q_others: Query<&Player, OthersFilter>,
+) {
+    let contenders = match_contenders(player_count.0, q_others);
+    let results = MatchResults {
+        duration_secs: scoreboard.elapsed(fixed.timestep()),
+        winner: scoreboard.winner(contenders),
+        players: scoreboard
+            .ranking(contenders)
+            .into_iter()
+            .map(|id| PlayerResult {
+                id,
+                kills: scoreboard.players[id].kills,
+                deaths: scoreboard.players[id].deaths,
+            })
+            .collect(),
+    };
+
+    println!("match over after {:.0}s", results.duration_secs);
+    for p in &results.players {
+        println!(
+            "  player {}: {} kills, {} deaths",
+            p.id + 1,
+            p.kills,
+            p.deaths
+        );
+    }
+
+    if let Some(path) = &results_path.0 {
+        let written = serde_json::to_string_pretty(&results)
+            .map_err(std::io::Error::other)
+            .and_then(|json| std::fs::write(path, json));
+        if let Err(e) = written {
+            warn!("failed to write match results to {}: {e}", path.display());
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use std::fs;
+
+use bevy::prelude::*;
+use woodeneye_bevy_ver::components::{Action, ActionBindings, Binding};
+use woodeneye_bevy_ver::helpers::{load_config, save_config};
+
+#[test]
+fn bindings_round_trip_through_ron() {
+    let path = std::env::temp_dir().join("woodeneye_bindings_test/bindings.ron");
+    let mut bindings = ActionBindings::default();
+    bindings.keyboards[0].rebind(Action::Jump, Binding::Key(KeyCode::KeyE));
+    save_config(&path, &bindings).unwrap();
+
+    let loaded: ActionBindings = load_config(&path);
+    let jump: Vec<Binding> = loaded.keyboards[0].bindings(Action::Jump).collect();
+    assert_eq!(jump, vec![Binding::Key(KeyCode::KeyE)]);
+    assert_eq!(loaded.global.0.len(), bindings.global.0.len());
+}
+
+#[test]
+fn malformed_bindings_fall_back_to_defaults() {
+    let path = std::env::temp_dir().join("woodeneye_bindings_test/malformed.ron");
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, "(keyboards: [oops").unwrap();
+
+    let loaded: ActionBindings = load_config(&path);
+    let forward: Vec<Binding> = loaded.keyboards[0].bindings(Action::MoveForward).collect();
+    assert_eq!(forward, vec![Binding::Key(KeyCode::KeyW)]);
+}
+
+#[test]
+fn every_keyboard_layout_has_its_own_scoreboard_key() {
+    let bindings = ActionBindings::default();
+    let keys: Vec<Vec<Binding>> = bindings
+        .keyboards
+        .iter()
+        .map(|table| table.bindings(Action::Scoreboard).collect())
+        .collect();
+    for (i, layout) in keys.iter().enumerate() {
+        assert_eq!(layout.len(), 1, "layout {i}");
+        // Holding it must not also drive another layout
+        for other in &bindings.keyboards {
+            let used = other.0.iter().filter(|(_, b)| *b == layout[0]).count();
+            assert!(used <= 1, "layout {i} shares {:?}", layout[0]);
+        }
+    }
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use bevy::prelude::*;
+use woodeneye_bevy_ver::components::{
+    Action, ActionState, Arena, MatchRules, Player, PlayerCamera, SimTick, TickInput,
+};
+use woodeneye_bevy_ver::events::{PlayerHit, PlayerKilled, ShotFired};
+use woodeneye_bevy_ver::systems::{handle_shooting, sample_tick_input, update_camera_transforms};
+
+fn firing(pressed: bool) -> ActionState {
+    let mut actions = ActionState::default();
+    actions.set(Action::Fire, pressed, pressed);
+    actions
+}
+
+fn spawn_shooter(app: &mut App, yaw: f32, pitch: f32) -> Entity {
+    let e = common::spawn_player(app, 0, Vec3::new(1.0, -12.0, 3.0));
+    let mut shooter = app.world_mut().entity_mut(e);
+    shooter.insert(firing(true));
+    let mut player = shooter.get_mut::<Player>().unwrap();
+    player.yaw = yaw;
+    player.pitch = pitch;
+    e
+}
+
+#[test]
+fn shots_leave_along_the_crosshair_ray() {
+    let mut app = App::new();
+    app.init_resource::<MatchRules>()
+        .init_resource::<Arena>()
+        .init_resource::<SimTick>()
+        .init_resource::<Time<Fixed>>()
+        .add_event::<ShotFired>()
+        .add_event::<PlayerHit>()
+        .add_event::<PlayerKilled>()
+        .add_systems(
+            Update,
+            (sample_tick_input, handle_shooting, update_camera_transforms).chain(),
+        );
+    spawn_shooter(&mut app, 0.7, -0.3);
+    let camera = app
+        .world_mut()
+        .spawn((Camera::default(), PlayerCamera { player_id: 0 }))
+        .id();
+    app.update();
+
+    let shots: Vec<&ShotFired> = app
+        .world()
+        .resource::<Events<ShotFired>>()
+        .iter_current_update_events()
+        .collect();
+    assert_eq!(shots.len(), 1);
+    // The crosshair sits on the camera's forward axis
+    let cam = app.world().get::<Transform>(camera).unwrap();
+    assert!(shots[0].origin.distance(cam.translation) < 1e-5);
+    assert!(shots[0].direction.distance(*cam.forward()) < 1e-5);
+}
+
+#[test]
+fn aim_is_held_until_the_shot_is_taken() {
+    let mut app = App::new();
+    app.add_systems(Update, sample_tick_input);
+    let shooter = spawn_shooter(&mut app, 0.7, -0.3);
+    app.update();
+
+    // The mouse keeps moving before the next fixed tick runs
+    let mut entity = app.world_mut().entity_mut(shooter);
+    entity.get_mut::<Player>().unwrap().yaw = 1.2;
+    *entity.get_mut::<ActionState>().unwrap() = firing(false);
+    app.update();
+
+    let input = app.world().get::<TickInput>(shooter).unwrap();
+    assert!(input.fire);
+    assert_eq!((input.yaw, input.pitch), (0.7, -0.3));
+}
@@ -0,0 +1,61 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::time::{TimePlugin, TimeUpdateStrategy};
+use woodeneye_bevy_ver::components::{Arena, Obstacle, PhysicsConfig, Player, TickInput, Velocity};
+use woodeneye_bevy_ver::helpers::ray_obstacle_distance;
+use woodeneye_bevy_ver::systems::update_physics;
+
+#[test]
+fn players_land_on_obstacles() {
+    let mut arena = Arena::empty(16.0);
+    arena.obstacles.push(Obstacle::new(
+        Vec3::new(-2.0, -16.0, -2.0),
+        Vec3::new(2.0, -10.0, 2.0),
+    ));
+    let mut app = App::new();
+    app.add_plugins(TimePlugin)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            10,
+        )))
+        .insert_resource(arena)
+        .init_resource::<PhysicsConfig>()
+        .add_systems(Update, update_physics);
+    let player = app
+        .world_mut()
+        .spawn((
+            Player {
+                id: 0,
+                yaw: 0.0,
+                pitch: 0.0,
+                radius: 0.5,
+                height: 1.5,
+                color: Color::WHITE,
+            },
+            Transform::from_translation(Vec3::new(0.0, -5.0, 0.0)),
+            Velocity(Vec3::ZERO),
+            TickInput::default(),
+        ))
+        .id();
+    for _ in 0..200 {
+        app.update();
+    }
+
+    // Feet rest on the top face instead of the arena floor
+    let head = app.world().get::<Transform>(player).unwrap().translation;
+    assert!((head.y - 1.5 - -10.0).abs() < 1e-3);
+    assert_eq!(app.world().get::<Velocity>(player).unwrap().y, 0.0);
+}
+
+#[test]
+fn rays_stop_at_the_nearest_obstacle() {
+    let obstacles = [
+        Obstacle::new(Vec3::new(-1.0, -1.0, -9.0), Vec3::new(1.0, 1.0, -8.0)),
+        Obstacle::new(Vec3::new(-1.0, -1.0, -5.0), Vec3::new(1.0, 1.0, -4.0)),
+    ];
+    assert_eq!(
+        ray_obstacle_distance(&obstacles, Vec3::ZERO, Vec3::NEG_Z),
+        Some(4.0)
+    );
+    assert_eq!(ray_obstacle_distance(&obstacles, Vec3::ZERO, Vec3::Z), None);
+}
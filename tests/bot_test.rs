@@ -0,0 +1,140 @@
+mod common;
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use woodeneye_bevy_ver::components::{
+    Arena, Bot, BotDifficulty, BotProfile, Health, InputAssignment, MatchRng, MatchRules, Obstacle,
+    SettingsRes, SimTick,
+};
+use woodeneye_bevy_ver::events::{PlayerHit, PlayerKilled, ShotFired};
+use woodeneye_bevy_ver::systems::{assign_bots, drive_bots, handle_shooting};
+
+const SHARPSHOOTER: BotProfile = BotProfile {
+    reaction_time: 0.5,
+    aim_error: 0.0,
+    turn_speed: 4.0,
+    fire_interval: 0.5,
+};
+
+fn spawn_player(app: &mut App, id: usize, pos: Vec3) -> Entity {
+    let e = common::spawn_player(app, id, pos);
+    app.world_mut()
+        .entity_mut(e)
+        .insert(InputAssignment::default());
+    e
+}
+
+fn bot_app(arena: Arena) -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / 60.0,
+        )))
+        .insert_resource(arena)
+        .init_resource::<MatchRules>()
+        .init_resource::<SimTick>()
+        .add_event::<ShotFired>()
+        .add_event::<PlayerHit>()
+        .add_event::<PlayerKilled>()
+        .add_systems(Update, (drive_bots, handle_shooting).chain());
+    // The first update only starts the clock
+    app.update();
+    app
+}
+
+#[test]
+fn bots_shoot_visible_opponents_after_their_reaction_time() {
+    let mut app = bot_app(Arena::empty(16.0));
+    let bot = spawn_player(&mut app, 0, Vec3::ZERO);
+    app.world_mut()
+        .entity_mut(bot)
+        .insert(Bot::new(SHARPSHOOTER, 3));
+    let target = spawn_player(&mut app, 1, Vec3::new(6.0, 0.0, -6.0));
+
+    let mut first_hit = None;
+    for frame in 1..=120 {
+        app.update();
+        if first_hit.is_none() && app.world().get::<Health>(target).unwrap().0 < 100.0 {
+            first_hit = Some(frame);
+        }
+    }
+    let first_hit = first_hit.expect("the bot never hit its target");
+    // Half a second of reaction at 60 ticks per second, then the turn
+    assert!(first_hit > 30, "hit on frame {first_hit}");
+}
+
+#[test]
+fn bots_wander_in_place_in_cramped_arenas() {
+    // No room to wander between the walls
+    let mut app = bot_app(Arena::empty(0.5));
+    let bot = spawn_player(&mut app, 0, Vec3::ZERO);
+    app.world_mut()
+        .entity_mut(bot)
+        .insert(Bot::new(SHARPSHOOTER, 3));
+    for _ in 0..10 {
+        app.update();
+    }
+    let goal = app.world().get::<Bot>(bot).unwrap().goal.unwrap();
+    assert_eq!((goal.x, goal.z), (0.0, 0.0));
+}
+
+#[test]
+fn obstacles_hide_opponents_from_bots() {
+    let mut arena = Arena::empty(16.0);
+    arena.obstacles.push(Obstacle::new(
+        Vec3::new(-16.0, -16.0, -4.0),
+        Vec3::new(16.0, 16.0, -3.0),
+    ));
+    let mut app = bot_app(arena);
+    let bot = spawn_player(&mut app, 0, Vec3::ZERO);
+    app.world_mut()
+        .entity_mut(bot)
+        .insert(Bot::new(SHARPSHOOTER, 3));
+    let target = spawn_player(&mut app, 1, Vec3::new(0.0, 0.0, -8.0));
+    for _ in 0..120 {
+        app.update();
+    }
+
+    assert_eq!(app.world().get::<Bot>(bot).unwrap().target, None);
+    assert_eq!(app.world().get::<Health>(target).unwrap().0, 100.0);
+}
+
+#[test]
+fn bots_take_only_slots_nobody_joined() {
+    let mut app = App::new();
+    let mut settings = SettingsRes::default();
+    settings.player_mut(0).bot = Some(BotDifficulty::Hard);
+    settings.player_mut(3).bot = None;
+    app.insert_resource(settings)
+        .init_resource::<MatchRng>()
+        .add_systems(Update, assign_bots);
+    let human = spawn_player(&mut app, 0, Vec3::ZERO);
+    app.world_mut()
+        .get_mut::<InputAssignment>(human)
+        .unwrap()
+        .mouse = true;
+    let easy = spawn_player(&mut app, 1, Vec3::ZERO);
+    let off = spawn_player(&mut app, 3, Vec3::ZERO);
+    app.world_mut()
+        .resource_mut::<SettingsRes>()
+        .player_mut(1)
+        .bot = Some(BotDifficulty::Easy);
+    app.update();
+
+    assert!(app.world().get::<Bot>(human).is_none());
+    assert!(app.world().get::<Bot>(off).is_none());
+    assert_eq!(
+        app.world().get::<Bot>(easy).unwrap().profile,
+        BotDifficulty::Easy.profile()
+    );
+
+    // A second player joins on the keyboard and takes the slot over
+    app.world_mut()
+        .get_mut::<InputAssignment>(easy)
+        .unwrap()
+        .keyboard = Some(1);
+    app.update();
+    assert!(app.world().get::<Bot>(easy).is_none());
+}
@@ -0,0 +1,198 @@
+mod common;
+
+use bevy::prelude::*;
+use woodeneye_bevy_ver::components::{
+    Arena, Dead, Health, MatchRng, MatchRules, Obstacle, Scoreboard, SimTick, SpawnPoints,
+    TickInput,
+};
+use woodeneye_bevy_ver::constants::EYE_HEIGHT;
+use woodeneye_bevy_ver::events::{HitZone, PlayerHit, PlayerKilled, PlayerRespawned, ShotFired};
+use woodeneye_bevy_ver::systems::{handle_shooting, record_kills, respawn_players};
+
+/// Puts the shooter's eye level with the targets' head centres.
+const SHOOTER: Vec3 = Vec3::new(0.0, -EYE_HEIGHT, 0.0);
+
+fn spawn_player(app: &mut App, id: usize, pos: Vec3, firing: bool) -> Entity {
+    let max_health = app.world().resource::<MatchRules>().max_health;
+    let e = common::spawn_player(app, id, pos);
+    app.world_mut().entity_mut(e).insert((
+        Health(max_health),
+        TickInput {
+            fire: firing,
+            ..default()
+        },
+    ));
+    e
+}
+
+fn combat_app(rules: MatchRules) -> App {
+    let mut app = App::new();
+    app.insert_resource(rules)
+        .init_resource::<Time>()
+        .init_resource::<Scoreboard>()
+        .insert_resource(MatchRng::seeded(7))
+        .init_resource::<SpawnPoints>()
+        .init_resource::<Arena>()
+        .init_resource::<SimTick>()
+        .add_event::<ShotFired>()
+        .add_event::<PlayerHit>()
+        .add_event::<PlayerKilled>()
+        .add_event::<PlayerRespawned>()
+        .add_systems(
+            Update,
+            (handle_shooting, respawn_players, record_kills).chain(),
+        );
+    app
+}
+
+#[test]
+fn headshots_and_body_shots_deal_different_damage() {
+    let mut app = combat_app(MatchRules::default());
+    spawn_player(&mut app, 0, SHOOTER, true);
+    // Ray passes through the head sphere of the first target and only the
+    // feet sphere of the second, which stands one unit higher
+    let head = spawn_player(&mut app, 1, Vec3::new(0.0, 0.0, -8.0), false);
+    let body = spawn_player(&mut app, 2, Vec3::new(0.0, 1.0, -5.0), false);
+    app.update();
+
+    assert!(app.world().get::<Dead>(head).is_some());
+    assert_eq!(app.world().get::<Health>(body).unwrap().0, 66.0);
+    assert!(app.world().get::<Dead>(body).is_none());
+
+    let scoreboard = app.world().resource::<Scoreboard>();
+    assert_eq!(scoreboard.players[0].kills, 1);
+    assert_eq!(scoreboard.players[1].deaths, 1);
+    assert_eq!(scoreboard.players[2].deaths, 0);
+    assert_eq!(scoreboard.winner(3), Some(0));
+}
+
+#[test]
+fn instagib_kills_and_respawns_immediately() {
+    let mut app = combat_app(MatchRules::instagib());
+    spawn_player(&mut app, 0, SHOOTER, true);
+    let target = spawn_player(&mut app, 1, Vec3::new(0.0, 0.0, -5.0), false);
+    app.update();
+
+    // Killed and respawned elsewhere within the same frame
+    let world = app.world();
+    assert!(world.get::<Dead>(target).is_none());
+    assert_eq!(world.get::<Health>(target).unwrap().0, 1.0);
+    assert_ne!(
+        world.get::<Transform>(target).unwrap().translation,
+        Vec3::new(0.0, 0.0, -5.0)
+    );
+}
+
+#[test]
+fn shooting_emits_hit_and_kill_events() {
+    let mut app = combat_app(MatchRules::default());
+    spawn_player(&mut app, 0, SHOOTER, true);
+    spawn_player(&mut app, 1, Vec3::new(0.0, 0.0, -8.0), false);
+    spawn_player(&mut app, 2, Vec3::new(0.0, 1.0, -5.0), false);
+    app.update();
+
+    let world = app.world();
+    let shots: Vec<&ShotFired> = world
+        .resource::<Events<ShotFired>>()
+        .iter_current_update_events()
+        .collect();
+    assert_eq!(shots.len(), 1);
+    assert_eq!(shots[0].shooter, 0);
+
+    let mut hits: Vec<&PlayerHit> = world
+        .resource::<Events<PlayerHit>>()
+        .iter_current_update_events()
+        .collect();
+    hits.sort_by_key(|h| h.target);
+    assert_eq!(hits.len(), 2);
+    assert_eq!((hits[0].target, hits[0].zone), (1, HitZone::Head));
+    assert_eq!((hits[1].target, hits[1].zone), (2, HitZone::Feet));
+    // The ray enters the head sphere half a unit in front of its centre
+    assert!(hits[0].location.distance(Vec3::new(0.0, 0.0, -7.5)) < 1e-4);
+
+    let kills: Vec<&PlayerKilled> = world
+        .resource::<Events<PlayerKilled>>()
+        .iter_current_update_events()
+        .collect();
+    assert_eq!(kills.len(), 1);
+    assert_eq!((kills[0].shooter, kills[0].target), (0, 1));
+}
+
+#[test]
+fn respawn_prefers_far_spawn_points_out_of_sight() {
+    let mut app = combat_app(MatchRules::instagib());
+    app.insert_resource(SpawnPoints(vec![
+        Vec3::new(0.0, -16.0, -14.0), // far, but in front of the shooter
+        Vec3::new(0.0, -16.0, 4.0),
+        Vec3::new(0.0, -16.0, 12.0),
+    ]));
+    spawn_player(&mut app, 0, SHOOTER, true);
+    let target = spawn_player(&mut app, 1, Vec3::new(0.0, 0.0, -5.0), false);
+    app.update();
+
+    assert_eq!(
+        app.world().get::<Transform>(target).unwrap().translation,
+        Vec3::new(0.0, -14.5, 12.0)
+    );
+}
+
+#[test]
+fn floor_respawns_are_reproducible_from_the_seed() {
+    let respawn = || {
+        let mut app = combat_app(MatchRules::instagib());
+        app.insert_resource(SpawnPoints(Vec::new()));
+        spawn_player(&mut app, 0, SHOOTER, true);
+        let target = spawn_player(&mut app, 1, Vec3::new(0.0, 0.0, -5.0), false);
+        app.update();
+        app.world().get::<Transform>(target).unwrap().translation
+    };
+    let first = respawn();
+    assert_eq!(first, respawn());
+    // Feet rest on the floor
+    assert_eq!(first.y, -14.5);
+}
+
+#[test]
+fn obstacles_block_shots() {
+    let mut app = combat_app(MatchRules::default());
+    app.world_mut()
+        .resource_mut::<Arena>()
+        .obstacles
+        .push(Obstacle::new(
+            Vec3::new(-1.0, -1.0, -4.0),
+            Vec3::new(1.0, 1.0, -3.0),
+        ));
+    spawn_player(&mut app, 0, SHOOTER, true);
+    let target = spawn_player(&mut app, 1, Vec3::new(0.0, 0.0, -8.0), false);
+    app.update();
+
+    assert_eq!(app.world().get::<Health>(target).unwrap().0, 100.0);
+    assert_eq!(
+        app.world()
+            .resource::<Events<PlayerHit>>()
+            .iter_current_update_events()
+            .count(),
+        0
+    );
+}
+
+#[test]
+fn blocked_spawn_points_fall_back_to_the_open_floor() {
+    let mut app = combat_app(MatchRules::instagib());
+    let mut arena = Arena::empty(16.0);
+    // A pillar over the only spawn point
+    arena.obstacles.push(Obstacle::new(
+        Vec3::new(-2.0, -16.0, 10.0),
+        Vec3::new(2.0, 0.0, 14.0),
+    ));
+    app.insert_resource(arena)
+        .insert_resource(SpawnPoints(vec![Vec3::new(0.0, -16.0, 12.0)]));
+    spawn_player(&mut app, 0, SHOOTER, true);
+    let target = spawn_player(&mut app, 1, Vec3::new(0.0, 0.0, -5.0), false);
+    app.update();
+
+    assert!(app.world().get::<Dead>(target).is_none());
+    let spawn = app.world().get::<Transform>(target).unwrap().translation;
+    assert_eq!(spawn.y, -14.5);
+    assert!(spawn.xz().distance(Vec2::new(0.0, 12.0)) > 2.0);
+}
@@ -0,0 +1,26 @@
+//! Fixtures shared by the integration tests.
+
+use bevy::prelude::*;
+use woodeneye_bevy_ver::components::{Health, Player, PreviousTranslation, TickInput, Velocity};
+
+/// Spawns a standing, unhurt player facing -Z at `pos` with no input held.
+/// Tests needing more insert it onto the returned entity.
+pub fn spawn_player(app: &mut App, id: usize, pos: Vec3) -> Entity {
+    app.world_mut()
+        .spawn((
+            Player {
+                id,
+                yaw: 0.0,
+                pitch: 0.0,
+                radius: 0.5,
+                height: 1.5,
+                color: Color::WHITE,
+            },
+            Transform::from_translation(pos),
+            PreviousTranslation(pos),
+            Velocity(Vec3::ZERO),
+            Health(100.0),
+            TickInput::default(),
+        ))
+        .id()
+}
@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+use woodeneye_bevy_ver::helpers::apply_dead_zone;
+
+#[test]
+fn sticks_inside_the_dead_zone_read_as_zero() {
+    assert_eq!(apply_dead_zone(Vec2::new(0.1, 0.05), 0.15), Vec2::ZERO);
+    assert_eq!(apply_dead_zone(Vec2::new(0.0, -0.15), 0.15), Vec2::ZERO);
+}
+
+#[test]
+fn the_rest_of_the_range_is_rescaled_to_reach_one() {
+    // Halfway between the dead zone and the rim reads as half
+    let half = apply_dead_zone(Vec2::new(0.6, 0.0), 0.2);
+    assert!((half - Vec2::new(0.5, 0.0)).length() < 1e-6);
+    // Full deflection keeps its direction and reaches exactly one
+    let full = apply_dead_zone(Vec2::new(0.0, -1.0), 0.2);
+    assert!((full - Vec2::new(0.0, -1.0)).length() < 1e-6);
+    // Diagonal corners of a square gate are capped at one
+    assert!((apply_dead_zone(Vec2::ONE, 0.2).length() - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn a_dead_zone_of_one_or_more_still_leaves_a_range() {
+    let out = apply_dead_zone(Vec2::new(1.0, 0.0), 1.0);
+    assert!(out.is_finite());
+    assert_eq!(out, Vec2::new(1.0, 0.0));
+}
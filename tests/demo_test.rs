@@ -0,0 +1,285 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use bevy::asset::AssetPlugin;
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use woodeneye_bevy_ver::components::{
+    Arena, Demo, DemoRecorder, Health, MapDef, MapHandle, MapPath, MatchRng, MatchRules,
+    PhysicsConfig, Player, Scoreboard, SimTick, TickInput, ViewTick,
+};
+use woodeneye_bevy_ver::constants::{DEMO_VERSION, EYE_HEIGHT, MAX_REWIND, PHYSICS_CONFIG_PATH};
+use woodeneye_bevy_ver::headless::{HeadlessConfig, headless_app, wander_script};
+use woodeneye_bevy_ver::helpers::{DemoError, load_demo, look_angles, save_demo};
+use woodeneye_bevy_ver::plugins::{GameAssetsPlugin, GameSet, ReplayPlugin, SimulationPlugin};
+
+fn temp_path(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join("woodeneye_demo_test");
+    fs::create_dir_all(&dir).unwrap();
+    dir.join(name)
+}
+
+/// Final positions, health and scores, enough to tell two matches apart.
+fn outcome(mut app: App) -> (Vec<Vec3>, Vec<f32>, Vec<(u32, u32)>) {
+    while app.should_exit().is_none() {
+        app.update();
+    }
+    let world = app.world_mut();
+    let mut players: Vec<(usize, Vec3, f32)> = world
+        .query::<(&Player, &Transform, &Health)>()
+        .iter(world)
+        .map(|(p, tf, health)| (p.id, tf.translation, health.0))
+        .collect();
+    players.sort_by_key(|(id, ..)| *id);
+    let scores = world
+        .resource::<Scoreboard>()
+        .players
+        .iter()
+        .map(|s| (s.kills, s.deaths))
+        .collect();
+    (
+        players.iter().map(|(_, pos, _)| *pos).collect(),
+        players.iter().map(|(.., health)| *health).collect(),
+        scores,
+    )
+}
+
+#[test]
+fn replays_reproduce_the_recorded_match() {
+    let path = temp_path("match.json");
+    let mut app = headless_app(
+        HeadlessConfig {
+            ticks: 600,
+            seed: Some(9),
+            ..default()
+        },
+        wander_script,
+    );
+    app.insert_resource(DemoRecorder::new(&path));
+    let recorded = outcome(app);
+    assert!(recorded.2.iter().any(|&(kills, _)| kills > 0));
+
+    let demo = load_demo(&path).unwrap();
+    assert_eq!((demo.seed, demo.ticks.len()), (9, 600));
+    let mut app = headless_app(
+        HeadlessConfig {
+            ticks: demo.ticks.len() as u64,
+            tick_rate: demo.tick_rate,
+            ..default()
+        },
+        // Ignored: the replay feeds the recording instead
+        |_, _| TickInput::default(),
+    );
+    app.add_plugins(ReplayPlugin(demo));
+    assert_eq!(outcome(app), recorded);
+}
+
+#[test]
+fn demos_from_other_versions_are_rejected() {
+    let path = temp_path("old.json");
+    let demo = Demo {
+        version: DEMO_VERSION + 1,
+        seed: 0,
+        tick_rate: 60.0,
+        map: "maps/default.map.ron".into(),
+        rules: default(),
+        maps: vec![(0, default())],
+        physics: vec![(0, default())],
+        players: 1,
+        ticks: vec![vec![TickInput::default()]],
+        view_ticks: vec![vec![None]],
+    };
+    save_demo(&path, &demo).unwrap();
+    assert!(matches!(load_demo(&path), Err(DemoError::Version(v)) if v == DEMO_VERSION + 1));
+}
+
+/// Once everyone has landed, player 1 dashes sideways and player 2 then fires
+/// once at where player 1 landed. Everyone else stands still.
+fn ambush(tick: u64, player: usize) -> TickInput {
+    match player {
+        0 => TickInput {
+            movement: Vec2::new(if (100..140).contains(&tick) { 1.0 } else { 0.0 }, 0.0),
+            ..default()
+        },
+        1 => {
+            // Eye to head centre, from one starting corner to the other
+            let (yaw, pitch) = look_angles(Vec3::new(16.0, -EYE_HEIGHT, 16.0));
+            TickInput {
+                fire: tick == 150,
+                yaw,
+                pitch,
+                ..default()
+            }
+        }
+        _ => TickInput::default(),
+    }
+}
+
+/// Player 2 sees the world as it was before the dash, like a lagging remote
+/// player.
+fn lag_player_two(mut commands: Commands, q: Query<(Entity, &Player)>) {
+    for (entity, player) in &q {
+        if player.id == 1 {
+            commands.entity(entity).insert(ViewTick(95));
+        }
+    }
+}
+
+#[test]
+fn replays_keep_lag_compensated_hits() {
+    let path = temp_path("lagged.json");
+    let mut app = headless_app(
+        HeadlessConfig {
+            ticks: 200,
+            seed: Some(4),
+            // Nothing between the starting corners
+            map: MapDef {
+                obstacles: Vec::new(),
+                ..default()
+            },
+            ..default()
+        },
+        ambush,
+    );
+    app.insert_resource(MatchRules {
+        max_rewind: MAX_REWIND,
+        ..default()
+    })
+    .insert_resource(DemoRecorder::new(&path))
+    .add_systems(FixedUpdate, lag_player_two.in_set(GameSet::Input));
+    let recorded = outcome(app);
+    // The shot hit player 1 where player 2 saw them
+    assert!(recorded.1[0] < 100.0);
+
+    let demo = load_demo(&path).unwrap();
+    assert_eq!(demo.view_tick(150, 1), Some(95));
+    assert_eq!(demo.view_tick(150, 0), None);
+    let mut app = headless_app(
+        HeadlessConfig {
+            ticks: demo.ticks.len() as u64,
+            tick_rate: demo.tick_rate,
+            map: MapDef {
+                obstacles: Vec::new(),
+                ..default()
+            },
+            ..default()
+        },
+        |_, _| TickInput::default(),
+    );
+    app.add_plugins(ReplayPlugin(demo));
+    assert_eq!(outcome(app), recorded);
+}
+
+/// The windowed game without the window: map and physics load from files in
+/// `assets` through the asset server.
+fn windowed_app(assets: &Path) -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin {
+            file_path: assets.display().to_string(),
+            ..default()
+        },
+        StatesPlugin,
+        SimulationPlugin,
+        GameAssetsPlugin,
+    ))
+    .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+        1.0 / 60.0,
+    )))
+    .insert_resource(Time::<Fixed>::from_hz(60.0))
+    .insert_resource(MatchRng::seeded(5))
+    .insert_resource(MapPath("maps/small.map.ron".into()));
+    app
+}
+
+fn write_map(assets: &Path, half_extent: f32) -> MapDef {
+    let map = MapDef {
+        half_extent,
+        obstacles: Vec::new(),
+        spawn_points: vec![Vec3::new(4.0, 0.0, 4.0)],
+        ..default()
+    };
+    fs::create_dir_all(assets.join("maps")).unwrap();
+    let text = ron::to_string(&map).unwrap();
+    fs::write(assets.join("maps/small.map.ron"), text).unwrap();
+    map
+}
+
+/// Updates until `tick` has run, waiting on the asset server in between.
+fn run_to_tick(app: &mut App, tick: u64) {
+    for _ in 0..10_000 {
+        if app.world().resource::<SimTick>().0 >= tick {
+            return;
+        }
+        app.update();
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    panic!("tick {tick} never ran");
+}
+
+fn positions(app: &mut App) -> Vec<Vec3> {
+    let world = app.world_mut();
+    let mut players: Vec<(usize, Vec3)> = world
+        .query::<(&Player, &Transform)>()
+        .iter(world)
+        .map(|(p, tf)| (p.id, tf.translation))
+        .collect();
+    players.sort_by_key(|(id, _)| *id);
+    players.into_iter().map(|(_, pos)| pos).collect()
+}
+
+#[test]
+fn windowed_demos_keep_the_map_and_physics_they_were_recorded_with() {
+    let assets = temp_path("assets");
+    let map = write_map(&assets, 10.0);
+    let physics = PhysicsConfig {
+        gravity: 40.0,
+        ..default()
+    };
+    fs::create_dir_all(assets.join("physics")).unwrap();
+    fs::write(
+        assets.join(PHYSICS_CONFIG_PATH),
+        ron::to_string(&physics).unwrap(),
+    )
+    .unwrap();
+
+    let mut app = windowed_app(&assets);
+    app.insert_resource(DemoRecorder::new(temp_path("windowed.json")));
+    run_to_tick(&mut app, 90);
+    // What the file watcher does when the map is saved mid-match
+    let wider = MapDef {
+        half_extent: 12.0,
+        ..map.clone()
+    };
+    let handle = app.world().resource::<MapHandle>().0.clone();
+    *app.world_mut()
+        .resource_mut::<Assets<MapDef>>()
+        .get_mut(&handle)
+        .unwrap() = wider.clone();
+    run_to_tick(&mut app, 120);
+
+    // The first tick already ran on the files, not the built-in defaults, and
+    // the edit was in play from the next tick on
+    let demo = app.world().resource::<DemoRecorder>().demo.clone().unwrap();
+    assert_eq!(demo.maps, [(0, map), (90, wider)]);
+    assert_eq!(demo.physics, [(0, physics)]);
+    assert_eq!(demo.ticks.len(), 120);
+    let recorded = positions(&mut app);
+
+    // The replay ignores edits to the files since
+    write_map(&assets, 14.0);
+    fs::write(
+        assets.join(PHYSICS_CONFIG_PATH),
+        ron::to_string(&PhysicsConfig::default()).unwrap(),
+    )
+    .unwrap();
+    let mut app = windowed_app(&assets);
+    app.add_plugins(ReplayPlugin(demo));
+    run_to_tick(&mut app, 120);
+    assert_eq!(app.world().resource::<Arena>().half_extent, 12.0);
+    assert_eq!(app.world().resource::<PhysicsConfig>().gravity, 40.0);
+    assert_eq!(positions(&mut app), recorded);
+}
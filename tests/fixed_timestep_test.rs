@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::time::{TimePlugin, TimeUpdateStrategy};
+use woodeneye_bevy_ver::components::{
+    Arena, PhysicsConfig, Player, PreviousTranslation, TickInput, Velocity,
+};
+use woodeneye_bevy_ver::systems::{save_previous_translations, update_physics};
+
+/// Runs one simulated second of a player running and jumping at the given
+/// frame rate and returns where they end up.
+fn simulate(frame_hz: u64) -> (Vec3, Vec3) {
+    let mut app = App::new();
+    app.add_plugins(TimePlugin)
+        .insert_resource(Time::<Fixed>::from_hz(50.0))
+        .init_resource::<Arena>()
+        .init_resource::<PhysicsConfig>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            1000 / frame_hz,
+        )))
+        .add_systems(
+            FixedUpdate,
+            (save_previous_translations, update_physics).chain(),
+        );
+    let player = app
+        .world_mut()
+        .spawn((
+            Player {
+                id: 0,
+                yaw: 0.0,
+                pitch: 0.0,
+                radius: 0.5,
+                height: 1.5,
+                color: Color::WHITE,
+            },
+            Transform::from_translation(Vec3::new(0.0, -14.5, 0.0)),
+            PreviousTranslation(Vec3::new(0.0, -14.5, 0.0)),
+            Velocity(Vec3::ZERO),
+            TickInput {
+                movement: Vec2::new(0.5, 1.0),
+                jump: true,
+                yaw: 0.3,
+                ..default()
+            },
+        ))
+        .id();
+    // The first update only starts the clock
+    for _ in 0..=frame_hz {
+        app.update();
+    }
+    let world = app.world();
+    (
+        world.get::<Transform>(player).unwrap().translation,
+        world.get::<PreviousTranslation>(player).unwrap().0,
+    )
+}
+
+#[test]
+fn physics_does_not_depend_on_frame_rate() {
+    let (slow, slow_previous) = simulate(25);
+    let (fast, fast_previous) = simulate(100);
+    assert_eq!(slow, fast);
+    assert_eq!(slow_previous, fast_previous);
+    // The player actually moved and left the ground
+    assert_ne!(slow, slow_previous);
+}
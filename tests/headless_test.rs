@@ -0,0 +1,151 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use woodeneye_bevy_ver::components::{
+    AppMode, Bot, Health, MatchRules, Player, Scoreboard, SettingsRes, SimTick, SpawnPoints,
+    TickInput, Velocity,
+};
+use woodeneye_bevy_ver::headless::{HeadlessConfig, headless_app, wander_script};
+
+/// Runs a headless match to completion and returns the final tick and scores.
+fn play(seed: u64) -> (u64, Vec<(u32, u32)>) {
+    let mut app = headless_app(
+        HeadlessConfig {
+            ticks: 600,
+            seed: Some(seed),
+            ..Default::default()
+        },
+        wander_script,
+    );
+    while app.should_exit().is_none() {
+        app.update();
+    }
+    let scores = app
+        .world()
+        .resource::<Scoreboard>()
+        .players
+        .iter()
+        .map(|s| (s.kills, s.deaths))
+        .collect();
+    (app.world().resource::<SimTick>().0, scores)
+}
+
+#[test]
+fn runs_for_the_requested_ticks_and_exits() {
+    let (ticks, _) = play(1);
+    assert_eq!(ticks, 600);
+}
+
+#[test]
+fn same_seed_same_match() {
+    let first = play(42);
+    // Something actually happened worth reproducing
+    assert!(first.1.iter().any(|&(kills, _)| kills > 0));
+    assert_eq!(play(42), first);
+}
+
+/// Plays a one-second match at 60 ticks per second, updating at `frame_hz`,
+/// and returns the tick it ended on and its clock.
+fn time_limited(frame_hz: u64) -> (u64, f32) {
+    let mut app = headless_app(
+        HeadlessConfig {
+            seed: Some(3),
+            ..Default::default()
+        },
+        wander_script,
+    );
+    app.insert_resource(MatchRules {
+        time_limit: Some(1.0),
+        ..Default::default()
+    })
+    .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        1000 / frame_hz,
+    )));
+    while app.should_exit().is_none() {
+        app.update();
+    }
+    let timestep = app.world().resource::<Time<Fixed>>().timestep();
+    let elapsed = app.world().resource::<Scoreboard>().elapsed(timestep);
+    (app.world().resource::<SimTick>().0, elapsed)
+}
+
+#[test]
+fn time_limits_end_on_the_same_tick_at_any_frame_rate() {
+    let slow = time_limited(25);
+    assert_eq!(slow.0, 60);
+    assert_eq!(time_limited(100), slow);
+}
+
+#[test]
+fn headless_matches_can_be_played_by_bots() {
+    let mut app = headless_app(
+        HeadlessConfig {
+            ticks: 1800,
+            seed: Some(3),
+            ..Default::default()
+        },
+        |_, _| TickInput::default(),
+    );
+    app.insert_resource(SettingsRes::default());
+    while app.should_exit().is_none() {
+        app.update();
+    }
+
+    let world = app.world_mut();
+    let bots: Vec<usize> = world
+        .query_filtered::<&Player, With<Bot>>()
+        .iter(world)
+        .map(|p| p.id)
+        .collect();
+    // The first slot is the scripted one, standing still
+    assert_eq!(bots.len(), 3);
+    assert!(!bots.contains(&0));
+    let kills: u32 = world
+        .resource::<Scoreboard>()
+        .players
+        .iter()
+        .map(|s| s.kills)
+        .sum();
+    assert!(kills > 0);
+}
+
+#[test]
+fn new_matches_start_everyone_afresh() {
+    let mut app = headless_app(
+        HeadlessConfig {
+            ticks: u64::MAX,
+            seed: Some(8),
+            ..Default::default()
+        },
+        wander_script,
+    );
+    for _ in 0..120 {
+        app.update();
+    }
+    let set_mode = |app: &mut App, mode: AppMode| {
+        app.world_mut()
+            .resource_mut::<NextState<AppMode>>()
+            .set(mode);
+        app.update();
+    };
+    set_mode(&mut app, AppMode::MatchOver);
+    set_mode(&mut app, AppMode::Playing);
+
+    let spawns = app.world().resource::<SpawnPoints>().0.clone();
+    let world = app.world_mut();
+    let mut players = world.query::<(&Player, &Transform, &Velocity, &Health)>();
+    for (player, tf, vel, health) in players.iter(world) {
+        let feet = tf.translation - Vec3::Y * player.height;
+        assert!(spawns.contains(&feet), "player {} at {feet}", player.id);
+        assert_eq!(vel.0, Vec3::ZERO);
+        assert_eq!(health.0, MatchRules::default().max_health);
+    }
+    assert!(
+        world
+            .resource::<Scoreboard>()
+            .players
+            .iter()
+            .all(|s| s.kills == 0 && s.deaths == 0)
+    );
+}
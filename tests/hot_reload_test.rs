@@ -0,0 +1,99 @@
+use bevy::asset::AssetPlugin;
+use bevy::prelude::*;
+use woodeneye_bevy_ver::components::{
+    Arena, MapColors, MapDef, MapHandle, Obstacle, PhysicsConfig, PhysicsConfigHandle, SpawnPoints,
+};
+use woodeneye_bevy_ver::helpers::{PhysicsConfigError, init_edges, parse_physics_config};
+use woodeneye_bevy_ver::systems::{apply_map, apply_physics_config};
+
+fn reload_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+        .init_asset::<MapDef>()
+        .init_asset::<PhysicsConfig>()
+        .init_resource::<PhysicsConfig>()
+        .init_resource::<Arena>()
+        .init_resource::<SpawnPoints>()
+        .init_resource::<MapColors>()
+        .insert_resource(init_edges(16.0, 16))
+        .add_systems(Update, (apply_map, apply_physics_config));
+    app
+}
+
+#[test]
+fn edited_physics_config_applies_live() {
+    let mut app = reload_app();
+    let handle = app
+        .world_mut()
+        .resource_mut::<Assets<PhysicsConfig>>()
+        .add(PhysicsConfig::default());
+    app.insert_resource(PhysicsConfigHandle(handle.clone()));
+    app.update();
+    app.update();
+
+    // What the file watcher does after a designer saves the file
+    app.world_mut()
+        .resource_mut::<Assets<PhysicsConfig>>()
+        .get_mut(&handle)
+        .unwrap()
+        .jump_velocity = 12.0;
+    app.update();
+    app.update();
+    assert_eq!(app.world().resource::<PhysicsConfig>().jump_velocity, 12.0);
+}
+
+#[test]
+fn edited_map_replaces_the_arena() {
+    let mut app = reload_app();
+    let handle = app
+        .world_mut()
+        .resource_mut::<Assets<MapDef>>()
+        .add(MapDef::default());
+    app.insert_resource(MapHandle(handle.clone()));
+    app.update();
+    app.update();
+
+    let mut maps = app.world_mut().resource_mut::<Assets<MapDef>>();
+    let map = maps.get_mut(&handle).unwrap();
+    map.obstacles = vec![Obstacle::new(Vec3::splat(-1.0), Vec3::splat(1.0))];
+    map.spawn_points.clear();
+    app.update();
+    app.update();
+
+    assert_eq!(app.world().resource::<Arena>().obstacles.len(), 1);
+    assert!(app.world().resource::<SpawnPoints>().0.is_empty());
+}
+
+#[test]
+fn shipped_physics_config_matches_defaults() {
+    let text = std::fs::read_to_string("assets/physics/default.physics.ron").unwrap();
+    assert_eq!(
+        parse_physics_config(&text).unwrap(),
+        PhysicsConfig::default()
+    );
+    for (text, bad) in [
+        ("(drag_rate: 0.0)", "drag_rate"),
+        ("(drag_rate: 1000.0)", "drag_rate"),
+        ("(move_mult: -60.0)", "move_mult"),
+        ("(move_mult: 1e9)", "move_mult"),
+        ("(gravity: -25.0)", "gravity"),
+        ("(gravity: inf)", "gravity"),
+        ("(jump_velocity: -8.0)", "jump_velocity"),
+        ("(jump_velocity: NaN)", "jump_velocity"),
+    ] {
+        assert!(
+            matches!(
+                parse_physics_config(text),
+                Err(PhysicsConfigError::Invalid { field, .. }) if field == bad
+            ),
+            "{text}"
+        );
+    }
+    // Zero is fine where it only turns something off
+    assert!(parse_physics_config("(gravity: 0.0, jump_velocity: 0.0)").is_ok());
+    let err = parse_physics_config("(gravity: -25.0)").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid physics config gravity: -25, expected 0 to 1000"
+    );
+}
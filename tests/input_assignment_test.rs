@@ -0,0 +1,61 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use woodeneye_bevy_ver::components::{ActionBindings, AppMode, InputAssignment, PlayerCount};
+use woodeneye_bevy_ver::systems::assign_input_devices;
+
+fn spawn_player(app: &mut App, id: usize, assignment: InputAssignment) -> Entity {
+    let e = common::spawn_player(app, id, Vec3::ZERO);
+    app.world_mut().entity_mut(e).insert(assignment);
+    e
+}
+
+#[test]
+fn unclaimed_keyboard_layout_joins_next_player() {
+    let mut app = App::new();
+    app.add_plugins(StatesPlugin)
+        .init_state::<AppMode>()
+        .insert_resource(PlayerCount(1))
+        .init_resource::<ActionBindings>()
+        .init_resource::<ButtonInput<KeyCode>>()
+        .init_resource::<ButtonInput<MouseButton>>()
+        .add_systems(
+            Update,
+            assign_input_devices.run_if(in_state(AppMode::Playing)),
+        );
+
+    let p0 = spawn_player(
+        &mut app,
+        0,
+        InputAssignment {
+            mouse: true,
+            keyboard: Some(0),
+            gamepad: None,
+        },
+    );
+    let p1 = spawn_player(&mut app, 1, InputAssignment::default());
+
+    // Arrow keys belong to the second layout
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(KeyCode::ArrowUp);
+    app.update();
+
+    assert_eq!(
+        app.world().get::<InputAssignment>(p0).unwrap().keyboard,
+        Some(0)
+    );
+    assert_eq!(
+        app.world().get::<InputAssignment>(p1).unwrap().keyboard,
+        Some(1)
+    );
+    assert_eq!(app.world().resource::<PlayerCount>().0, 2);
+
+    // Keys of an already claimed layout do not join anyone
+    let mut kb = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+    kb.clear();
+    kb.press(KeyCode::KeyW);
+    app.update();
+    assert_eq!(app.world().resource::<PlayerCount>().0, 2);
+}
@@ -0,0 +1,190 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use woodeneye_bevy_ver::components::{
+    Arena, Health, MatchRules, Player, PositionHistory, RemotePlayer, SentInput, SimTick,
+    TickInput, ViewTick,
+};
+use woodeneye_bevy_ver::constants::{EYE_HEIGHT, MAX_REWIND};
+use woodeneye_bevy_ver::events::{PlayerHit, PlayerKilled, ShotFired};
+use woodeneye_bevy_ver::systems::{
+    advance_sim_tick, apply_remote_inputs, handle_shooting, record_position_history,
+};
+
+fn player(id: usize) -> Player {
+    Player {
+        id,
+        yaw: 0.0, // facing -Z
+        pitch: 0.0,
+        radius: 0.5,
+        height: 1.5,
+        color: Color::WHITE,
+    }
+}
+
+fn hits(app: &App) -> usize {
+    app.world()
+        .resource::<Events<PlayerHit>>()
+        .iter_current_update_events()
+        .count()
+}
+
+#[test]
+fn lagging_shooters_hit_targets_where_they_saw_them() {
+    let mut app = App::new();
+    app.init_resource::<MatchRules>()
+        .init_resource::<Arena>()
+        .init_resource::<SimTick>()
+        .add_event::<ShotFired>()
+        .add_event::<PlayerHit>()
+        .add_event::<PlayerKilled>()
+        .add_systems(Update, handle_shooting);
+    // The eye sits level with the target's head
+    let shooter = app
+        .world_mut()
+        .spawn((
+            player(0),
+            Transform::from_xyz(0.0, -EYE_HEIGHT, 0.0),
+            TickInput {
+                fire: true,
+                ..default()
+            },
+            ViewTick(5),
+        ))
+        .id();
+    // Straight ahead on tick 5, off to the side since
+    let history = VecDeque::from([
+        (4, Vec3::new(-1.0, 0.0, -8.0)),
+        (5, Vec3::new(0.0, 0.0, -8.0)),
+        (6, Vec3::new(1.0, 0.0, -8.0)),
+    ]);
+    app.world_mut().spawn((
+        player(1),
+        Transform::from_xyz(2.0, 0.0, -8.0),
+        Health(100.0),
+        PositionHistory(history),
+    ));
+    app.update();
+    assert_eq!(hits(&app), 1);
+
+    // Without the lag the shot goes where the target is now
+    let mut entity = app.world_mut().entity_mut(shooter);
+    entity.remove::<ViewTick>();
+    entity.get_mut::<TickInput>().unwrap().fire = true;
+    app.update();
+    assert_eq!(hits(&app), 0);
+}
+
+#[test]
+fn history_reaches_back_only_as_far_as_the_rewind_window() {
+    let mut app = App::new();
+    app.insert_resource(MatchRules {
+        max_rewind: 0.05,
+        ..default()
+    })
+    .insert_resource(Time::<Fixed>::from_hz(60.0))
+    .init_resource::<SimTick>()
+    .add_systems(Update, record_position_history);
+    let target = app
+        .world_mut()
+        .spawn((Transform::default(), PositionHistory::default()))
+        .id();
+    for tick in 1..=10 {
+        app.world_mut().resource_mut::<SimTick>().0 = tick;
+        app.world_mut()
+            .get_mut::<Transform>(target)
+            .unwrap()
+            .translation
+            .x = tick as f32;
+        app.update();
+    }
+
+    let history = app.world().get::<PositionHistory>(target).unwrap();
+    // Three ticks of rewind at 60 per second, plus the latest
+    assert_eq!(history.0.len(), 4);
+    assert_eq!(history.at(8).unwrap().x, 8.0);
+    assert_eq!(history.at(2).unwrap().x, 7.0);
+    assert!(history.at(11).is_none());
+}
+
+#[test]
+fn remote_shots_rewind_to_the_snapshot_the_client_saw() {
+    let mut app = App::new();
+    app.init_resource::<MatchRules>()
+        .init_resource::<Arena>()
+        .init_resource::<SimTick>()
+        .insert_resource(Time::<Fixed>::from_hz(60.0))
+        .add_event::<ShotFired>()
+        .add_event::<PlayerHit>()
+        .add_event::<PlayerKilled>()
+        .add_systems(
+            Update,
+            (
+                apply_remote_inputs,
+                handle_shooting,
+                advance_sim_tick,
+                record_position_history,
+            )
+                .chain(),
+        );
+    let shooter = app
+        .world_mut()
+        .spawn((
+            player(0),
+            Transform::from_xyz(0.0, -EYE_HEIGHT, 0.0),
+            TickInput::default(),
+            RemotePlayer {
+                addr: "127.0.0.1:7777".parse().unwrap(),
+                queue: VecDeque::new(),
+                last_seq: 0,
+                input: TickInput::default(),
+                ack: 0,
+                last_heard: 0.0,
+            },
+        ))
+        .id();
+    // Sweeps across the line of fire, straight ahead after tick 5
+    let target = app
+        .world_mut()
+        .spawn((player(1), Transform::default(), Health(100.0)))
+        .insert(PositionHistory::default())
+        .id();
+    let shoot = |app: &mut App, seq: u64, view_tick: u64| {
+        let x = app.world().resource::<SimTick>().0 as f32 - 4.0;
+        app.world_mut()
+            .get_mut::<Transform>(target)
+            .unwrap()
+            .translation = Vec3::new(x, 0.0, -8.0);
+        let mut remote = app.world_mut().get_mut::<RemotePlayer>(shooter).unwrap();
+        remote.queue.push_back(SentInput {
+            seq,
+            input: TickInput {
+                fire: view_tick > 0,
+                ..default()
+            },
+            view_tick,
+        });
+        app.update();
+        hits(app)
+    };
+    for seq in 1..=8 {
+        assert_eq!(shoot(&mut app, seq, 0), 0);
+    }
+    // Snapshots 4 and 6 showed the target to either side, 5 straight ahead
+    assert_eq!(shoot(&mut app, 9, 4), 0);
+    assert_eq!(shoot(&mut app, 10, 6), 0);
+    assert_eq!(shoot(&mut app, 11, 5), 1);
+}
+
+#[test]
+fn rewind_windows_are_capped() {
+    assert_eq!(MatchRules::clamp_rewind(f32::NAN), 0.0);
+    assert_eq!(MatchRules::clamp_rewind(-1.0), 0.0);
+    assert_eq!(MatchRules::clamp_rewind(f32::INFINITY), MAX_REWIND);
+    let rules: MatchRules = ron::from_str(
+        "(max_health: 100, body_damage: 34, head_damage: 100, respawn_delay: 3, \
+         score_limit: None, time_limit: None, max_rewind: 1e30)",
+    )
+    .unwrap();
+    assert_eq!(rules.max_rewind, MAX_REWIND);
+}
@@ -0,0 +1,65 @@
+use woodeneye_bevy_ver::components::Arena;
+use woodeneye_bevy_ver::helpers::{MapError, parse_map};
+
+#[test]
+fn shipped_map_matches_built_in_arena() {
+    let text = std::fs::read_to_string("assets/maps/default.map.ron").unwrap();
+    let map = parse_map(&text).unwrap();
+    let arena = Arena::default();
+    assert_eq!(map.half_extent, arena.half_extent);
+    assert_eq!(map.obstacles.len(), arena.obstacles.len());
+    for (a, b) in map.obstacles.iter().zip(&arena.obstacles) {
+        assert!(a.min.abs_diff_eq(b.min, 1e-4) && a.max.abs_diff_eq(b.max, 1e-4));
+    }
+}
+
+#[test]
+fn invalid_entries_are_named() {
+    let err = parse_map(
+        "(half_extent: 8.0, obstacles: [
+            (min: (0.0, 0.0, 0.0), max: (1.0, 1.0, 1.0)),
+            (min: (2.0, 0.0, 0.0), max: (1.0, 1.0, 1.0)),
+        ])",
+    )
+    .unwrap_err();
+    assert!(
+        matches!(&err, MapError::Invalid { entry, .. } if entry == "obstacles[1]"),
+        "{err}"
+    );
+
+    let err = parse_map(
+        "(half_extent: 8.0,
+          obstacles: [(min: (-1.0, -8.0, -1.0), max: (1.0, 0.0, 1.0))],
+          spawn_points: [(4.0, -8.0, 4.0), (0.0, -4.0, 0.0)])",
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid map entry spawn_points[1]: [0, -4, 0] is inside obstacles[0]"
+    );
+}
+
+#[test]
+fn syntax_errors_report_the_position() {
+    let err = parse_map("(half_extent: 8.0,\n grid_lines: -1)").unwrap_err();
+    assert!(matches!(err, MapError::Parse(_)));
+    assert!(err.to_string().starts_with("malformed map at 2:"), "{err}");
+}
+
+#[test]
+fn maps_without_obstacles_are_open() {
+    let map = parse_map("(half_extent: 8.0, spawn_points: [(0.0, -8.0, 0.0)])").unwrap();
+    assert!(map.obstacles.is_empty());
+    assert_eq!(map.half_extent, 8.0);
+}
+
+#[test]
+fn small_maps_need_only_their_extent() {
+    // The built-in spawn points and grid fit a bigger arena
+    let map = parse_map("(half_extent: 8.0)").unwrap();
+    assert!(map.spawn_points.is_empty());
+    assert_eq!(map.grid_lines, 8);
+
+    let map = parse_map("(half_extent: 8.0, grid_lines: 4)").unwrap();
+    assert_eq!(map.grid_lines, 4);
+}
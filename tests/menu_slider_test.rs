@@ -0,0 +1,76 @@
+use woodeneye_bevy_ver::components::{MenuItem, PlayerSettings};
+use woodeneye_bevy_ver::helpers::{set_slider, slider_fraction, slider_range, step_slider};
+
+const SLIDERS: [MenuItem; 3] = [
+    MenuItem::Sensitivity,
+    MenuItem::Crosshair,
+    MenuItem::GamepadSensitivity,
+];
+
+fn value(settings: &PlayerSettings, item: MenuItem) -> f32 {
+    match item {
+        MenuItem::Sensitivity => settings.sensitivity,
+        MenuItem::Crosshair => settings.crosshair_half,
+        MenuItem::GamepadSensitivity => settings.gamepad_sensitivity,
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn steps_stop_at_the_range_ends() {
+    for item in SLIDERS {
+        let (min, max, step) = slider_range(item).unwrap();
+        let mut settings = PlayerSettings::default();
+        let count = ((max - min) / step).ceil() as i32;
+
+        step_slider(&mut settings, item, count + 5);
+        assert_eq!(value(&settings, item), max, "{item:?}");
+        assert_eq!(slider_fraction(&settings, item), 1.0);
+        // Back down from the top lands a whole step below it
+        step_slider(&mut settings, item, -1);
+        assert!((value(&settings, item) - (max - step)).abs() < 1e-6);
+
+        step_slider(&mut settings, item, -(count + 5));
+        assert_eq!(value(&settings, item), min, "{item:?}");
+        assert_eq!(slider_fraction(&settings, item), 0.0);
+        step_slider(&mut settings, item, 1);
+        assert!((value(&settings, item) - (min + step)).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn whole_steps_add_up_without_drifting() {
+    // Twenty steps of 0.02 from the bottom of the crosshair range
+    let mut settings = PlayerSettings::default();
+    set_slider(&mut settings, MenuItem::Crosshair, 0.0);
+    for _ in 0..20 {
+        step_slider(&mut settings, MenuItem::Crosshair, 1);
+    }
+    assert!((settings.crosshair_half - 0.42).abs() < 1e-5);
+    step_slider(&mut settings, MenuItem::Crosshair, -20);
+    assert!((settings.crosshair_half - 0.02).abs() < 1e-5);
+}
+
+#[test]
+fn clicks_past_the_track_ends_clamp() {
+    for item in SLIDERS {
+        let (min, max, _) = slider_range(item).unwrap();
+        let mut settings = PlayerSettings::default();
+        set_slider(&mut settings, item, -0.5);
+        assert_eq!(value(&settings, item), min, "{item:?}");
+        set_slider(&mut settings, item, 1.5);
+        assert_eq!(value(&settings, item), max, "{item:?}");
+        set_slider(&mut settings, item, f32::NAN);
+        assert_eq!(value(&settings, item), max, "{item:?}");
+    }
+}
+
+#[test]
+fn other_items_are_not_sliders() {
+    let mut settings = PlayerSettings::default();
+    step_slider(&mut settings, MenuItem::Bot, 1);
+    set_slider(&mut settings, MenuItem::Bot, 1.0);
+    assert_eq!(slider_range(MenuItem::Bot), None);
+    assert_eq!(slider_fraction(&settings, MenuItem::Bot), 0.0);
+    assert_eq!(settings.bot, PlayerSettings::default().bot);
+}
@@ -0,0 +1,283 @@
+use std::net::{SocketAddr, UdpSocket};
+use std::time::Duration;
+
+use bevy::prelude::*;
+use woodeneye_bevy_ver::components::{
+    AppMode, NetClient, NetMessage, NetServer, PhysicsConfig, Player, PlayerScore, Predicted,
+    RemotePlayer, Replicated, Scoreboard, SentInput, Snapshot, TickInput,
+};
+use woodeneye_bevy_ver::constants::{
+    MAX_PITCH, MAX_PLAYER_COUNT, NET_INPUT_QUEUE, NET_PROTOCOL_VERSION,
+};
+use woodeneye_bevy_ver::headless::{HeadlessConfig, headless_app};
+use woodeneye_bevy_ver::helpers::encode_message;
+
+fn idle(_: u64, _: usize) -> TickInput {
+    TickInput::default()
+}
+
+/// Runs forward for a second, then stands still.
+fn dash(tick: u64, _: usize) -> TickInput {
+    TickInput {
+        movement: if tick < 60 {
+            Vec2::new(0.0, -1.0)
+        } else {
+            Vec2::ZERO
+        },
+        ..default()
+    }
+}
+
+fn app(script: fn(u64, usize) -> TickInput, physics: PhysicsConfig) -> App {
+    headless_app(
+        HeadlessConfig {
+            ticks: u64::MAX,
+            seed: Some(5),
+            physics,
+            ..default()
+        },
+        script,
+    )
+}
+
+fn server() -> (App, SocketAddr) {
+    let mut server = app(idle, default());
+    let net = NetServer::bind("127.0.0.1:0").unwrap();
+    let addr = net.socket.local_addr().unwrap();
+    server.insert_resource(net);
+    (server, addr)
+}
+
+fn client(server: SocketAddr, script: fn(u64, usize) -> TickInput) -> App {
+    client_with(server, script, default())
+}
+
+fn client_with(
+    server: SocketAddr,
+    script: fn(u64, usize) -> TickInput,
+    physics: PhysicsConfig,
+) -> App {
+    let mut client = app(script, physics);
+    client.insert_resource(NetClient::connect(server).unwrap());
+    client
+}
+
+/// Updates every app still running in turn, giving loopback datagrams a
+/// moment to land.
+fn run(apps: &mut [&mut App], rounds: usize) {
+    for _ in 0..rounds {
+        for app in apps.iter_mut().filter(|app| app.should_exit().is_none()) {
+            app.update();
+        }
+        std::thread::sleep(Duration::from_millis(1));
+    }
+}
+
+fn player_id(client: &App) -> Option<usize> {
+    client.world().resource::<NetClient>().player_id
+}
+
+fn position(app: &mut App, id: usize) -> Vec3 {
+    let world = app.world_mut();
+    world
+        .query::<(&Player, &Transform)>()
+        .iter(world)
+        .find(|(p, _)| p.id == id)
+        .unwrap()
+        .1
+        .translation
+}
+
+#[test]
+fn clients_play_their_slot_on_the_server_and_see_each_other() {
+    let (mut server, addr) = server();
+    let mut runner = client(addr, |_, _| TickInput {
+        movement: Vec2::new(0.0, -1.0),
+        ..default()
+    });
+    let mut watcher = client(addr, idle);
+    run(&mut [&mut server, &mut runner, &mut watcher], 20);
+
+    let runner_id = player_id(&runner).expect("the runner was not seated");
+    let watcher_id = player_id(&watcher).expect("the watcher was not seated");
+    // The server's own player 1 keeps its slot
+    assert!(runner_id != 0 && watcher_id != 0 && runner_id != watcher_id);
+
+    let start = position(&mut server, runner_id);
+    run(&mut [&mut server, &mut runner, &mut watcher], 100);
+    let moved = position(&mut server, runner_id);
+    assert!(
+        start.xz().distance(moved.xz()) > 2.0,
+        "the server did not move the runner from {start}"
+    );
+    // The watcher mirrors the runner from snapshots, at most a tick behind
+    let seen = position(&mut watcher, runner_id);
+    assert!(seen.distance(moved) < 0.5, "seen at {seen}, is at {moved}");
+    let world = watcher.world_mut();
+    let replicated: Vec<usize> = world
+        .query_filtered::<&Player, With<Replicated>>()
+        .iter(world)
+        .map(|p| p.id)
+        .collect();
+    assert!(replicated.contains(&runner_id) && !replicated.contains(&watcher_id));
+}
+
+#[test]
+fn a_full_server_turns_clients_away() {
+    let (mut server, addr) = server();
+    let mut clients: Vec<App> = (0..4).map(|_| client(addr, idle)).collect();
+    let mut apps: Vec<&mut App> = std::iter::once(&mut server)
+        .chain(clients.iter_mut())
+        .collect();
+    run(&mut apps, 30);
+
+    let (seated, turned_away): (Vec<&App>, Vec<&App>) =
+        clients.iter().partition(|c| player_id(c).is_some());
+    // Player 1 on the server is local, leaving three slots
+    assert_eq!(seated.len(), 3);
+    assert!(turned_away[0].should_exit().is_some_and(|e| e.is_error()));
+}
+
+/// Correction and unacknowledged inputs of a client's own player, zero
+/// until it is seated.
+fn prediction(client: &mut App) -> (Vec3, usize) {
+    let world = client.world_mut();
+    world
+        .query::<&Predicted>()
+        .single(world)
+        .map_or((Vec3::ZERO, 0), |p| (p.correction, p.pending.len()))
+}
+
+#[test]
+fn the_local_player_is_predicted_exactly() {
+    let (mut server, addr) = server();
+    let mut runner = client(addr, dash);
+    let mut apps = [&mut server, &mut runner];
+    let mut largest = 0.0f32;
+    for _ in 0..90 {
+        run(&mut apps, 1);
+        largest = largest.max(prediction(apps[1]).0.length());
+    }
+
+    // Same inputs through the same physics: the server never disagrees
+    assert!(largest < 1e-4, "corrected by {largest}");
+    let (_, pending) = prediction(&mut runner);
+    assert!(pending <= 2, "{pending} inputs unacknowledged");
+}
+
+#[test]
+fn mispredictions_are_reconciled_with_the_server() {
+    let (mut server, addr) = server();
+    // This client believes it runs twice as fast as the server lets it
+    let physics = PhysicsConfig::default();
+    let mut runner = client_with(
+        addr,
+        dash,
+        PhysicsConfig {
+            move_mult: physics.move_mult * 2.0,
+            ..physics
+        },
+    );
+    let mut apps = [&mut server, &mut runner];
+    let mut corrected = false;
+    for _ in 0..150 {
+        run(&mut apps, 1);
+        corrected |= prediction(apps[1]).0.length() > 0.01;
+    }
+    assert!(corrected);
+
+    let id = player_id(&runner).unwrap();
+    let (on_server, on_client) = (position(&mut server, id), position(&mut runner, id));
+    assert!(
+        on_client.distance(on_server) < 0.05,
+        "client at {on_client}, server at {on_server}"
+    );
+}
+
+#[test]
+fn remote_input_is_sanitized_and_queued_within_bounds() {
+    let (mut server, addr) = server();
+    // A hand-rolled client: one oversized input, with a yaw too large for an
+    // f32, and a flood of inputs while the match is paused
+    let cheat = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let hello = NetMessage::Hello {
+        version: NET_PROTOCOL_VERSION,
+    };
+    cheat.send_to(&encode_message(&hello), addr).unwrap();
+    run(&mut [&mut server], 5);
+    let input = NetMessage::Input(vec![SentInput {
+        seq: 1,
+        input: TickInput {
+            movement: Vec2::new(50.0, 0.0),
+            yaw: 0.5,
+            pitch: 3.0,
+            ..default()
+        },
+        view_tick: 0,
+    }]);
+    let bytes = String::from_utf8(encode_message(&input))
+        .unwrap()
+        .replace("0.5", "1e39");
+    cheat.send_to(bytes.as_bytes(), addr).unwrap();
+    run(&mut [&mut server], 5);
+
+    let world = server.world_mut();
+    let (player, input) = world
+        .query_filtered::<(&Player, &TickInput), With<RemotePlayer>>()
+        .single(world)
+        .unwrap();
+    assert_eq!(input.movement, Vec2::new(1.0, 0.0));
+    assert_eq!(input.yaw, 0.0);
+    assert_eq!(input.pitch, MAX_PITCH);
+    assert_eq!(player.pitch, MAX_PITCH);
+
+    server
+        .world_mut()
+        .resource_mut::<NextState<AppMode>>()
+        .set(AppMode::Menu);
+    run(&mut [&mut server], 2);
+    let flood = NetMessage::Input((2..100).map(|seq| SentInput { seq, ..default() }).collect());
+    cheat.send_to(&encode_message(&flood), addr).unwrap();
+    run(&mut [&mut server], 5);
+    let world = server.world_mut();
+    let remote = world.query::<&RemotePlayer>().single(world).unwrap();
+    assert_eq!(remote.last_seq, 99);
+    assert!(remote.queue.len() <= NET_INPUT_QUEUE);
+}
+
+#[test]
+fn malformed_scoreboards_keep_one_score_per_slot() {
+    let fake = UdpSocket::bind("127.0.0.1:0").unwrap();
+    fake.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+    let mut client = client(fake.local_addr().unwrap(), idle);
+    // Its hello says where it is
+    run(&mut [&mut client], 2);
+    let mut buf = [0; 1500];
+    let (_, client_addr) = fake.recv_from(&mut buf).unwrap();
+    for (tick, slots) in [(1, 1), (2, MAX_PLAYER_COUNT + 3)] {
+        let snapshot = NetMessage::Snapshot(Snapshot {
+            tick,
+            ack: 0,
+            match_over: false,
+            players: Vec::new(),
+            scoreboard: Scoreboard {
+                players: vec![
+                    PlayerScore {
+                        kills: 2,
+                        deaths: 1
+                    };
+                    slots
+                ],
+                ticks: tick,
+            },
+        });
+        fake.send_to(&encode_message(&snapshot), client_addr)
+            .unwrap();
+        run(&mut [&mut client], 3);
+
+        let scoreboard = client.world().resource::<Scoreboard>();
+        assert_eq!(scoreboard.ticks, tick);
+        assert_eq!(scoreboard.players.len(), MAX_PLAYER_COUNT);
+        assert_eq!(scoreboard.players[0].kills, 2);
+    }
+}
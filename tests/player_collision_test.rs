@@ -0,0 +1,100 @@
+mod common;
+
+use bevy::prelude::*;
+use woodeneye_bevy_ver::components::{
+    Arena, PhysicsConfig, Predicted, PreviousTranslation, Velocity,
+};
+use woodeneye_bevy_ver::systems::{predict_local_player, resolve_player_collisions};
+
+fn collision_app() -> App {
+    let mut app = App::new();
+    app.insert_resource(Arena::empty(16.0))
+        .init_resource::<PhysicsConfig>()
+        .add_systems(Update, resolve_player_collisions);
+    app
+}
+
+fn spawn_player(app: &mut App, id: usize, pos: Vec3, vel: Vec3) -> Entity {
+    let e = common::spawn_player(app, id, pos);
+    app.world_mut().entity_mut(e).insert(Velocity(vel));
+    e
+}
+
+fn translation(app: &App, e: Entity) -> Vec3 {
+    app.world().get::<Transform>(e).unwrap().translation
+}
+
+#[test]
+fn overlapping_players_push_apart_evenly() {
+    let mut app = collision_app();
+    let a = spawn_player(&mut app, 0, Vec3::new(0.0, 0.0, 0.0), Vec3::ZERO);
+    let b = spawn_player(&mut app, 1, Vec3::new(0.6, 0.0, 0.0), Vec3::ZERO);
+    app.update();
+
+    assert!((translation(&app, a).x + 0.2).abs() < 1e-5);
+    assert!((translation(&app, b).x - 0.8).abs() < 1e-5);
+}
+
+#[test]
+fn players_can_stand_on_heads() {
+    let mut app = collision_app();
+    let below = spawn_player(&mut app, 0, Vec3::new(0.0, 0.0, 0.0), Vec3::ZERO);
+    // Feet sphere sinks into the head sphere from above while falling
+    let above = spawn_player(
+        &mut app,
+        1,
+        Vec3::new(0.1, 1.5, 0.0),
+        Vec3::new(0.0, -3.0, 0.0),
+    );
+    app.update();
+
+    assert_eq!(translation(&app, below), Vec3::ZERO);
+    let feet = translation(&app, above) + Vec3::Y * (0.5 - 1.5);
+    assert!((feet.distance(Vec3::ZERO) - 1.0).abs() < 1e-5);
+    assert_eq!(app.world().get::<Velocity>(above).unwrap().y, 0.0);
+}
+
+#[test]
+fn resolution_ignores_spawn_order() {
+    let positions = [
+        Vec3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.7, 0.0, 0.2),
+        Vec3::new(0.3, 0.2, 0.6),
+    ];
+    let run = |order: [usize; 3]| {
+        let mut app = collision_app();
+        let mut entities = [Entity::PLACEHOLDER; 3];
+        for id in order {
+            entities[id] = spawn_player(&mut app, id, positions[id], Vec3::ZERO);
+        }
+        app.update();
+        entities.map(|e| translation(&app, e))
+    };
+    assert_eq!(run([0, 1, 2]), run([2, 0, 1]));
+}
+
+#[test]
+fn prediction_pushes_off_replicated_players_like_the_server() {
+    let mut server = collision_app();
+    spawn_player(&mut server, 0, Vec3::ZERO, Vec3::ZERO);
+    let on_server = spawn_player(&mut server, 1, Vec3::new(0.6, 0.0, 0.0), Vec3::ZERO);
+    server.update();
+
+    let mut client = App::new();
+    client
+        .insert_resource(Arena::empty(16.0))
+        .init_resource::<PhysicsConfig>()
+        .init_resource::<Time>()
+        .add_systems(Update, predict_local_player);
+    let other = spawn_player(&mut client, 0, Vec3::ZERO, Vec3::ZERO);
+    let local = spawn_player(&mut client, 1, Vec3::new(0.6, 0.0, 0.0), Vec3::ZERO);
+    client
+        .world_mut()
+        .entity_mut(local)
+        .insert((Predicted::default(), PreviousTranslation::default()));
+    client.update();
+
+    assert!(translation(&client, local).abs_diff_eq(translation(&server, on_server), 1e-5));
+    // Where others are is the server's to say
+    assert_eq!(translation(&client, other), Vec3::ZERO);
+}
@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use woodeneye_bevy_ver::plugins::{GameSet, SimulationPlugin};
+
+#[derive(Resource, Default)]
+struct Trace(Vec<&'static str>);
+
+fn probe(name: &'static str) -> impl FnMut(ResMut<Trace>) {
+    move |mut trace: ResMut<Trace>| trace.0.push(name)
+}
+
+#[test]
+fn game_sets_run_in_order_and_accept_our_systems() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin, SimulationPlugin))
+        .insert_resource(Time::<Fixed>::from_hz(50.0))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            20,
+        )))
+        .init_resource::<Trace>();
+    // Registered in reverse to show the sets, not insertion order, decide
+    for (set, name) in [
+        (GameSet::Match, "tick match"),
+        (GameSet::Combat, "tick combat"),
+        (GameSet::Physics, "tick physics"),
+        (GameSet::Input, "tick input"),
+    ] {
+        app.add_systems(FixedUpdate, probe(name).in_set(set));
+    }
+    app.add_systems(
+        FixedUpdate,
+        probe("between physics and combat")
+            .after(GameSet::Physics)
+            .before(GameSet::Combat),
+    );
+    for (set, name) in [
+        (GameSet::Ui, "ui"),
+        (GameSet::Camera, "camera"),
+        (GameSet::Input, "input"),
+    ] {
+        app.add_systems(Update, probe(name).in_set(set));
+    }
+
+    // The first update only starts the clock
+    app.update();
+    app.world_mut().resource_mut::<Trace>().0.clear();
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<Trace>().0,
+        [
+            "tick input",
+            "tick physics",
+            "between physics and combat",
+            "tick combat",
+            "tick match",
+            "input",
+            "camera",
+            "ui",
+        ]
+    );
+}
@@ -0,0 +1,259 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use woodeneye_bevy_ver::components::{
+    Action, ActionState, AppMode, DemoRecorder, Health, InputScript, MapDef, MatchRules, Player,
+    RollbackSession, Scoreboard, SimTick, TickInput,
+};
+use woodeneye_bevy_ver::constants::{EYE_HEIGHT, NET_TIMEOUT, ROLLBACK_WINDOW};
+use woodeneye_bevy_ver::headless::{HeadlessConfig, headless_app, wander_script};
+use woodeneye_bevy_ver::helpers::{LoopbackTransport, look_angles};
+use woodeneye_bevy_ver::plugins::GameSet;
+use woodeneye_bevy_ver::systems::sample_tick_input;
+
+/// Players 1 and 2 wander and shoot; the other slots stay out of the match.
+fn duel(tick: u64, player: usize) -> TickInput {
+    if player < 2 {
+        wander_script(tick, player)
+    } else {
+        TickInput::default()
+    }
+}
+
+/// Every player's position and health after each tick, overwritten when a
+/// rollback runs the tick again.
+#[derive(Resource, Default)]
+struct Trace(BTreeMap<u64, Vec<(Vec3, f32)>>);
+
+fn trace(tick: Res<SimTick>, mut trace: ResMut<Trace>, q: Query<(&Player, &Transform, &Health)>) {
+    let mut players: Vec<_> = q.iter().collect();
+    players.sort_by_key(|(p, ..)| p.id);
+    let state = players
+        .iter()
+        .map(|(_, tf, health)| (tf.translation, health.0))
+        .collect();
+    trace.0.insert(tick.0, state);
+}
+
+/// Player 1 fires along the diagonal at player 2's corner once everyone has
+/// landed. Player 2 steps out of the line of fire, turns back just short of
+/// it, and later walks into it.
+fn dodge(tick: u64, player: usize) -> TickInput {
+    match player {
+        0 => {
+            let (yaw, pitch) = look_angles(Vec3::new(-16.0, -EYE_HEIGHT, -16.0));
+            TickInput {
+                fire: tick >= 80,
+                yaw,
+                pitch,
+                ..default()
+            }
+        }
+        1 => {
+            let x = match tick {
+                70..85 | 100..110 => 1.0,
+                85..100 | 130.. => -1.0,
+                _ => 0.0,
+            };
+            TickInput {
+                movement: Vec2::new(x, 0.0),
+                ..default()
+            }
+        }
+        _ => TickInput::default(),
+    }
+}
+
+fn app(session: Option<RollbackSession>) -> App {
+    app_with(default(), duel, session)
+}
+
+fn app_with(
+    config: HeadlessConfig,
+    script: fn(u64, usize) -> TickInput,
+    session: Option<RollbackSession>,
+) -> App {
+    let mut app = headless_app(
+        HeadlessConfig {
+            ticks: u64::MAX,
+            seed: Some(11),
+            ..config
+        },
+        script,
+    );
+    app.init_resource::<Trace>()
+        .add_systems(FixedUpdate, trace.after(GameSet::Match));
+    if let Some(session) = session {
+        app.insert_resource(session);
+    }
+    app
+}
+
+fn peers(latency: u64) -> (App, App) {
+    let (a, b) = LoopbackTransport::pair(latency);
+    (
+        app(Some(RollbackSession::new(a, 0, vec![0, 1]))),
+        app(Some(RollbackSession::new(b, 1, vec![0, 1]))),
+    )
+}
+
+#[test]
+fn late_inputs_are_rolled_back_to_the_same_match_on_both_peers() {
+    let (mut a, mut b) = peers(5);
+    let mut reference = app(None);
+    for _ in 0..300 {
+        a.update();
+        b.update();
+        reference.update();
+    }
+
+    // Guesses were wrong and had to be replayed
+    assert!(a.world().resource::<RollbackSession>().rollbacks > 0);
+    assert!(b.world().resource::<RollbackSession>().rollbacks > 0);
+    // Ticks old enough for every input to have arrived match a local match
+    let expected = &reference.world().resource::<Trace>().0;
+    for peer in [&a, &b] {
+        let trace = &peer.world().resource::<Trace>().0;
+        for tick in 1..=250 {
+            assert_eq!(trace[&tick], expected[&tick], "tick {tick}");
+        }
+    }
+    let kills = |app: &App| {
+        let scoreboard = app.world().resource::<Scoreboard>();
+        scoreboard
+            .players
+            .iter()
+            .map(|s| s.kills)
+            .collect::<Vec<_>>()
+    };
+    assert!(kills(&reference).iter().sum::<u32>() > 0);
+    assert_eq!(kills(&a), kills(&b));
+}
+
+/// Stands in for a person at the local devices: turns a little every frame
+/// and walks forward from frame 50 on.
+fn play_locally(
+    mut frame: Local<u32>,
+    session: Res<RollbackSession>,
+    mut q: Query<(&mut Player, &mut ActionState)>,
+) {
+    *frame += 1;
+    for (mut player, mut actions) in &mut q {
+        if player.id == session.local {
+            player.yaw += 0.01;
+            actions.set(Action::MoveForward, *frame >= 50, *frame == 50);
+        }
+    }
+}
+
+#[test]
+fn rollbacks_keep_the_live_local_input() {
+    let (mut a, mut b) = peers(5);
+    a.world_mut().remove_resource::<InputScript>();
+    a.add_systems(PreUpdate, (play_locally, sample_tick_input).chain());
+    for _ in 0..200 {
+        a.update();
+        b.update();
+    }
+
+    // Every tick plays what the devices said on its frame, with no snapping
+    // back to inputs already played before a rollback
+    let session = a.world().resource::<RollbackSession>();
+    assert!(session.rollbacks > 0);
+    let played = &session.inputs[session.local];
+    for pair in played.windows(2) {
+        assert!((pair[1].yaw - pair[0].yaw - 0.01).abs() < 1e-4, "{pair:?}");
+    }
+    assert_eq!(played.last().unwrap().movement, Vec2::Y);
+}
+
+#[test]
+fn a_silent_peer_holds_the_match_back() {
+    let (mut a, _b) = peers(0);
+    for _ in 0..100 {
+        a.update();
+    }
+    assert_eq!(a.world().resource::<SimTick>().0, ROLLBACK_WINDOW);
+}
+
+#[test]
+fn the_match_goes_on_without_a_peer_that_went_quiet() {
+    let (mut a, mut b) = peers(0);
+    for _ in 0..60 {
+        a.update();
+        b.update();
+    }
+    let stalled = a.world().resource::<SimTick>().0 + ROLLBACK_WINDOW;
+    // Frames are a tick long, so this is past the timeout
+    let frames = (NET_TIMEOUT * 60.0) as u64 + 60;
+    for _ in 0..frames {
+        a.update();
+    }
+
+    assert_eq!(a.world().resource::<RollbackSession>().players, vec![0]);
+    let now = a.world().resource::<SimTick>().0;
+    assert!(now > stalled + 30, "stuck on tick {now}");
+    // Player 2 no longer wanders, just slides to a stop
+    let trace = &a.world().resource::<Trace>().0;
+    let slid = trace[&now][1].0.distance(trace[&(now - 30)][1].0);
+    assert!(slid < 0.1, "moved {slid}");
+}
+
+#[test]
+fn the_match_ends_only_once_the_peers_confirm_it() {
+    // Nothing between the starting corners
+    let config = || HeadlessConfig {
+        map: MapDef {
+            obstacles: Vec::new(),
+            ..default()
+        },
+        ..default()
+    };
+    let (ta, tb) = LoopbackTransport::pair(5);
+    let mut a = app_with(
+        config(),
+        dodge,
+        Some(RollbackSession::new(ta, 0, vec![0, 1])),
+    );
+    let mut b = app_with(
+        config(),
+        dodge,
+        Some(RollbackSession::new(tb, 1, vec![0, 1])),
+    );
+    let mut reference = app_with(config(), dodge, None);
+    let rules = MatchRules {
+        score_limit: Some(1),
+        ..default()
+    };
+    for (app, name) in [(&mut a, "a"), (&mut b, "b"), (&mut reference, "reference")] {
+        let path = std::env::temp_dir().join(format!("woodeneye_rollback_{name}.json"));
+        app.insert_resource(rules.clone())
+            .insert_resource(DemoRecorder::new(path));
+    }
+    let over = |app: &App| *app.world().resource::<State<AppMode>>().get() == AppMode::MatchOver;
+    for _ in 0..300 {
+        a.update();
+        b.update();
+        reference.update();
+    }
+
+    // Peer 1 guessed that player 2 kept walking into the line of fire, but
+    // the match goes on until player 2 really does
+    let end = |app: &App| {
+        let scoreboard = app.world().resource::<Scoreboard>();
+        let kills: Vec<_> = scoreboard.players.iter().map(|s| s.kills).collect();
+        (app.world().resource::<SimTick>().0, scoreboard.ticks, kills)
+    };
+    assert!(over(&reference) && over(&a) && over(&b));
+    assert!(end(&reference).0 > 120);
+    assert_eq!(end(&a), end(&reference));
+    assert_eq!(end(&b), end(&reference));
+    // Ticks run again replace what was recorded for them
+    let ticks = |app: &App| {
+        let recorder = app.world().resource::<DemoRecorder>();
+        recorder.demo.as_ref().unwrap().ticks.clone()
+    };
+    assert_eq!(ticks(&a).len() as u64, end(&reference).0);
+    assert_eq!(ticks(&a), ticks(&reference));
+    assert_eq!(ticks(&b), ticks(&reference));
+}
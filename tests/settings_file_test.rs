@@ -0,0 +1,87 @@
+use std::fs;
+use std::path::PathBuf;
+
+use woodeneye_bevy_ver::components::SettingsRes;
+use woodeneye_bevy_ver::constants::{MAX_DEAD_ZONE, MAX_PLAYER_COUNT, SETTINGS_VERSION};
+use woodeneye_bevy_ver::helpers::{load_settings, save_config};
+
+fn temp_path(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join("woodeneye_settings_test");
+    fs::create_dir_all(&dir).unwrap();
+    dir.join(name)
+}
+
+#[test]
+fn missing_settings_file_uses_defaults() {
+    let settings = load_settings(temp_path("does_not_exist.ron"));
+    assert_eq!(settings.version, SETTINGS_VERSION);
+    assert_eq!(settings.players.len(), MAX_PLAYER_COUNT);
+}
+
+#[test]
+fn settings_are_per_player_and_round_trip() {
+    let path = temp_path("round_trip.ron");
+    let mut settings = load_settings(&path);
+    settings.player_mut(2).sensitivity = 0.01;
+    save_config(&path, &settings).unwrap();
+
+    let loaded = load_settings(&path);
+    assert_eq!(loaded.player(2).sensitivity, 0.01);
+    assert_eq!(loaded.player(0).sensitivity, settings.player(0).sensitivity);
+}
+
+#[test]
+fn partial_and_future_settings_files_are_handled() {
+    let path = temp_path("partial.ron");
+    fs::write(&path, "(players: [(sensitivity: 0.004)])").unwrap();
+    let loaded = load_settings(&path);
+    assert_eq!(loaded.players.len(), MAX_PLAYER_COUNT);
+    assert_eq!(loaded.player(0).sensitivity, 0.004);
+    // Fields and slots left out match a fresh install
+    let fresh = SettingsRes::default();
+    for id in 0..MAX_PLAYER_COUNT {
+        assert_eq!(loaded.player(id).bot, fresh.player(id).bot);
+    }
+
+    let path = temp_path("future.ron");
+    fs::write(&path, "(version: 999, players: [(sensitivity: 0.004)])").unwrap();
+    let loaded = load_settings(&path);
+    assert_eq!(loaded.version, SETTINGS_VERSION);
+    assert_ne!(loaded.player(0).sensitivity, 0.004);
+}
+
+#[test]
+fn dead_zones_are_clamped_into_the_usable_range() {
+    let path = temp_path("dead_zone.ron");
+    fs::write(
+        &path,
+        "(players: [(gamepad_dead_zone: 1.5), (gamepad_dead_zone: -0.2)])",
+    )
+    .unwrap();
+    let loaded = load_settings(&path);
+    assert_eq!(loaded.player(0).gamepad_dead_zone, MAX_DEAD_ZONE);
+    assert_eq!(loaded.player(1).gamepad_dead_zone, 0.0);
+}
+
+#[test]
+fn sliders_are_clamped_like_the_menu_clamps_them() {
+    let path = temp_path("sliders.ron");
+    fs::write(
+        &path,
+        "(players: [
+            (sensitivity: NaN, crosshair_half: 50.0, gamepad_sensitivity: -3.0),
+            (sensitivity: inf, gamepad_dead_zone: NaN),
+        ])",
+    )
+    .unwrap();
+    let loaded = load_settings(&path);
+    let fresh = SettingsRes::default();
+    assert_eq!(loaded.player(0).sensitivity, fresh.player(0).sensitivity);
+    assert_eq!(loaded.player(0).crosshair_half, 1.0);
+    assert_eq!(loaded.player(0).gamepad_sensitivity, 0.5);
+    assert_eq!(loaded.player(1).sensitivity, 0.02);
+    assert_eq!(
+        loaded.player(1).gamepad_dead_zone,
+        fresh.player(1).gamepad_dead_zone
+    );
+}
@@ -0,0 +1,138 @@
+mod common;
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use woodeneye_bevy_ver::components::{
+    Action, ActionState, Arena, Obstacle, PeerPlayer, PlayerCount, Spectator, SpectatorMode,
+};
+use woodeneye_bevy_ver::helpers::eye_position;
+use woodeneye_bevy_ver::systems::update_spectators;
+
+use common::spawn_player;
+
+fn spectator_app(arena: Arena) -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )))
+        .insert_resource(arena)
+        .insert_resource(PlayerCount(2))
+        .add_systems(Update, update_spectators);
+    app
+}
+
+fn spawn_spectator(app: &mut App, spectator: Spectator) -> Entity {
+    app.world_mut()
+        .spawn((Transform::default(), spectator, ActionState::default()))
+        .id()
+}
+
+/// Runs one frame with `action` just pressed on the spectator.
+fn press(app: &mut App, spectator: Entity, action: Action) {
+    let mut actions = ActionState::default();
+    actions.set(action, true, true);
+    app.world_mut().entity_mut(spectator).insert(actions);
+    app.update();
+    app.world_mut()
+        .entity_mut(spectator)
+        .insert(ActionState::default());
+}
+
+fn mode(app: &App, spectator: Entity) -> SpectatorMode {
+    app.world().get::<Spectator>(spectator).unwrap().mode
+}
+
+#[test]
+fn fire_cycles_through_players_and_back_to_free_fly() {
+    let mut app = spectator_app(Arena::empty(16.0));
+    spawn_player(&mut app, 1, Vec3::new(4.0, -10.0, 2.0));
+    spawn_player(&mut app, 0, Vec3::new(-3.0, -14.5, 6.0));
+    let spectator = spawn_spectator(&mut app, Spectator::default());
+
+    press(&mut app, spectator, Action::Fire);
+    assert_eq!(mode(&app, spectator), SpectatorMode::Follow(0));
+    // First person looks from the player's own eye
+    let cam = app.world().get::<Transform>(spectator).unwrap();
+    assert_eq!(cam.translation, eye_position(Vec3::new(-3.0, -14.5, 6.0)));
+
+    press(&mut app, spectator, Action::Fire);
+    assert_eq!(mode(&app, spectator), SpectatorMode::Follow(1));
+    press(&mut app, spectator, Action::Fire);
+    assert_eq!(mode(&app, spectator), SpectatorMode::FreeFly);
+}
+
+#[test]
+fn idle_slots_are_skipped() {
+    let mut app = spectator_app(Arena::empty(16.0));
+    app.insert_resource(PlayerCount(1));
+    spawn_player(&mut app, 0, Vec3::ZERO);
+    let peer = spawn_player(&mut app, 1, Vec3::ZERO);
+    app.world_mut().entity_mut(peer).insert(PeerPlayer);
+    // Nobody joined slots 2 and 3
+    spawn_player(&mut app, 2, Vec3::ZERO);
+    spawn_player(&mut app, 3, Vec3::ZERO);
+    let spectator = spawn_spectator(&mut app, Spectator::default());
+
+    press(&mut app, spectator, Action::Fire);
+    assert_eq!(mode(&app, spectator), SpectatorMode::Follow(0));
+    press(&mut app, spectator, Action::Fire);
+    assert_eq!(mode(&app, spectator), SpectatorMode::Follow(1));
+    press(&mut app, spectator, Action::Fire);
+    assert_eq!(mode(&app, spectator), SpectatorMode::FreeFly);
+}
+
+#[test]
+fn third_person_stays_in_front_of_walls() {
+    let mut arena = Arena::empty(16.0);
+    arena.obstacles.push(Obstacle::new(
+        Vec3::new(-2.0, -2.0, 2.0),
+        Vec3::new(2.0, 2.0, 3.0),
+    ));
+    let mut app = spectator_app(arena);
+    spawn_player(&mut app, 0, Vec3::ZERO);
+    let spectator = spawn_spectator(
+        &mut app,
+        Spectator {
+            mode: SpectatorMode::Follow(0),
+            ..default()
+        },
+    );
+
+    press(&mut app, spectator, Action::Jump);
+    assert!(
+        app.world()
+            .get::<Spectator>(spectator)
+            .unwrap()
+            .third_person
+    );
+    let cam = app.world().get::<Transform>(spectator).unwrap();
+    // Behind the player, pulled in from four units to just short of the wall
+    assert!(cam.translation.z > 1.0 && cam.translation.z < 2.0);
+    assert!(cam.forward().dot(Vec3::NEG_Z) > 0.99);
+}
+
+#[test]
+fn free_fly_moves_where_the_camera_looks() {
+    let mut app = spectator_app(Arena::empty(16.0));
+    let spectator = spawn_spectator(
+        &mut app,
+        Spectator {
+            pitch: 0.0,
+            ..default()
+        },
+    );
+    let mut actions = ActionState::default();
+    actions.set(Action::MoveForward, true, false);
+    app.world_mut().entity_mut(spectator).insert(actions);
+    // The first update only starts the clock
+    for _ in 0..6 {
+        app.update();
+    }
+
+    let cam = app.world().get::<Transform>(spectator).unwrap();
+    assert!(cam.translation.z < -5.0, "at {}", cam.translation);
+    assert!(cam.translation.x.abs() < 1e-4);
+}